@@ -1,4 +1,5 @@
 use bindgen::callbacks::ParseCallbacks;
+use serde::Deserialize;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -9,6 +10,11 @@ struct CustomRenamer;
 
 static KSON_LIB_VERSION: &str = "0.2.0";
 
+/// Semver range of `libkson` this crate is known to be compatible with. A
+/// user-supplied source tree or prebuilt binary outside this range is rejected
+/// rather than linked silently and failing at runtime.
+static KSON_LIB_VERSION_REQ: &str = "^0.2.0";
+
 impl ParseCallbacks for CustomRenamer {
     // Necessary to get rid of the `libkson` vs. `kson` difference depending on the target OS
     fn item_name(&self, original_item_name: &str) -> Option<String> {
@@ -20,34 +26,177 @@ impl ParseCallbacks for CustomRenamer {
     }
 }
 
+/// How the native `libkson` artifacts are obtained, selected by `KSON_STRATEGY`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Strategy {
+    /// No explicit strategy: fall back to the historical precedence chain
+    /// (`KSON_ROOT_SOURCE_DIR` → `KSON_PREBUILT_BIN_DIR` → download).
+    Auto,
+    /// Always download a prebuilt archive from the release server.
+    Download,
+    /// Always build from a source tree given by `KSON_ROOT_SOURCE_DIR`.
+    Source,
+    /// Link against an already-installed library under `KSON_LIB_LOCATION`,
+    /// copying and downloading nothing.
+    System,
+}
+
+fn resolve_strategy() -> Strategy {
+    match env::var("KSON_STRATEGY").ok().as_deref() {
+        None | Some("") => Strategy::Auto,
+        Some("download") => Strategy::Download,
+        Some("source") => Strategy::Source,
+        Some("system") => Strategy::System,
+        Some(other) => {
+            panic!("unknown KSON_STRATEGY `{other}`; expected `download`, `source`, or `system`")
+        }
+    }
+}
+
+/// Where the build should look for the bindgen header and whether it has already
+/// taken responsibility for emitting link-search directives (the `system`
+/// strategy links against a user-supplied location rather than `OUT_DIR`).
+struct BuildLayout {
+    header_dir: PathBuf,
+    system: bool,
+}
+
+/// Declarative control over the generated FFI surface, read from
+/// `bindings.toml`. Each section maps onto the corresponding bindgen allowlist
+/// so maintainers can pin exactly which `kson_*` symbols are exposed and keep
+/// the generated API stable across `libkson` releases.
+#[derive(Debug, Default, Deserialize)]
+struct BindingsConfig {
+    /// Patterns passed to `allowlist_type`.
+    #[serde(default)]
+    types: Vec<String>,
+    /// Patterns passed to `allowlist_function`.
+    #[serde(default)]
+    functions: Vec<String>,
+    /// Patterns passed to `allowlist_var`.
+    #[serde(default)]
+    variables: Vec<String>,
+    /// Patterns passed to `opaque_type` (Kotlin-native runtime structs).
+    #[serde(default)]
+    opaque: Vec<String>,
+    /// Patterns passed to `constified_enum_module`.
+    #[serde(default)]
+    enums: Vec<String>,
+}
+
+fn load_bindings_config(manifest_dir: &Path) -> BindingsConfig {
+    let path = manifest_dir.join("bindings.toml");
+    println!("cargo:rerun-if-changed={}", path.display());
+    match fs::read_to_string(&path) {
+        Ok(text) => toml::from_str(&text).expect("failed to parse bindings.toml"),
+        Err(_) => BindingsConfig::default(),
+    }
+}
+
 fn get_kson_artifacts(
+    strategy: Strategy,
     use_dynamic_linking: bool,
     out_dir: &Path,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<BuildLayout, Box<dyn std::error::Error>> {
     let kson_root_env_var = "KSON_ROOT_SOURCE_DIR";
     let kson_prebuild_env_var = "KSON_PREBUILT_BIN_DIR";
-    if let Ok(kson_root) = env::var(kson_root_env_var) {
-        build_kson_from_source(Path::new(&kson_root), use_dynamic_linking, out_dir)
-    } else {
-        if let Ok(prebuilt_root) = env::var(kson_prebuild_env_var) {
-            for entry in fs::read_dir(&prebuilt_root)? {
-                let entry = entry?;
-                let source_path = entry.path();
-                if source_path.is_file() {
-                    let file_name = source_path.file_name().unwrap();
-                    let dest_path = out_dir.join(file_name);
-                    fs::copy(&source_path, &dest_path)?;
-                    println!("cargo:rerun-if-changed={}", source_path.display());
+
+    match strategy {
+        Strategy::System => {
+            let location = env::var("KSON_LIB_LOCATION").map_err(|_| {
+                "KSON_STRATEGY=system requires KSON_LIB_LOCATION to point at an installed libkson"
+            })?;
+            // Link against the already-installed library in place; nothing is
+            // copied into OUT_DIR.
+            let location = PathBuf::from(location);
+            verify_lib_version(&location)?;
+            println!("cargo:rustc-link-search=native={}", location.display());
+            return Ok(BuildLayout {
+                header_dir: location,
+                system: true,
+            });
+        }
+        Strategy::Source => {
+            let kson_root = env::var(kson_root_env_var).map_err(|_| {
+                format!("KSON_STRATEGY=source requires `{kson_root_env_var}` to point at a kson source tree")
+            })?;
+            build_kson_from_source(Path::new(&kson_root), use_dynamic_linking, out_dir)?;
+            verify_lib_version(out_dir)?;
+        }
+        Strategy::Download => {
+            download_prebuilt_kson(use_dynamic_linking, out_dir)?;
+        }
+        Strategy::Auto => {
+            if let Ok(kson_root) = env::var(kson_root_env_var) {
+                build_kson_from_source(Path::new(&kson_root), use_dynamic_linking, out_dir)?;
+                verify_lib_version(out_dir)?;
+            } else if let Ok(prebuilt_root) = env::var(kson_prebuild_env_var) {
+                for entry in fs::read_dir(&prebuilt_root)? {
+                    let entry = entry?;
+                    let source_path = entry.path();
+                    if source_path.is_file() {
+                        let file_name = source_path.file_name().unwrap();
+                        let dest_path = out_dir.join(file_name);
+                        fs::copy(&source_path, &dest_path)?;
+                        println!("cargo:rerun-if-changed={}", source_path.display());
+                    }
                 }
+                verify_lib_version(out_dir)?;
+            } else if let Err(e) = download_prebuilt_kson(use_dynamic_linking, out_dir) {
+                panic!(
+                    "failed to download prebuilt kson: {e}\nset the `{kson_prebuild_env_var}` variable to the path of compatible kson binaries, or the `{kson_root_env_var}` variable to the path of a compatible kson source tree (if you prefer to build kson from source)"
+                );
             }
-        } else if let Err(e) = download_prebuilt_kson(use_dynamic_linking, out_dir) {
-            panic!(
-                "failed to download prebuilt kson: {e}\nset the `{kson_prebuild_env_var}` variable to the path of compatible kson binaries, or the `{kson_root_env_var}` variable to the path of a compatible kson source tree (if you prefer to build kson from source)"
-            );
         }
+    }
+
+    Ok(BuildLayout {
+        header_dir: out_dir.to_path_buf(),
+        system: false,
+    })
+}
+
+/// SHA-256 digests of the published prebuilt archives, keyed by
+/// `(shared_or_static, cpu_arch, os)` for the current [`KSON_LIB_VERSION`].
+/// A key absent here is downloaded without verification (with a build
+/// warning) so a brand-new platform isn't blocked before its digest is
+/// recorded. The table holds no entries, so every download takes that path.
+static PREBUILT_SHA256: &[(&str, &str, &str, &str)] = &[
+    // (shared_or_static, cpu_arch, os, sha256-hex)
+];
+
+/// Check a user-supplied libkson against [`KSON_LIB_VERSION_REQ`], reading a
+/// `.version` file shipped alongside the binaries. A missing file is warned
+/// about and skipped (older drops didn't ship one); an out-of-range version is
+/// a hard error with the expected and found versions.
+fn verify_lib_version(dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let version_file = dir.join(".version");
+    let Ok(contents) = fs::read_to_string(&version_file) else {
+        println!(
+            "cargo:warning=no .version file found alongside libkson in {}; skipping compatibility check",
+            dir.display()
+        );
+        return Ok(());
+    };
 
-        Ok(())
+    let found = semver::Version::parse(contents.trim())?;
+    let req = semver::VersionReq::parse(KSON_LIB_VERSION_REQ)?;
+    if !req.matches(&found) {
+        return Err(format!(
+            "incompatible libkson: found {found}, but this crate requires {KSON_LIB_VERSION_REQ}"
+        )
+        .into());
     }
+    Ok(())
+}
+
+fn expected_digest(shared_or_static: &str, cpu_arch: &str, os: &str) -> Option<&'static str> {
+    PREBUILT_SHA256
+        .iter()
+        .find(|(kind, arch, target_os, _)| {
+            *kind == shared_or_static && *arch == cpu_arch && *target_os == os
+        })
+        .map(|(_, _, _, digest)| *digest)
 }
 
 fn download_prebuilt_kson(
@@ -71,17 +220,134 @@ fn download_prebuilt_kson(
     };
 
     fs::create_dir_all(out_dir)?;
+    let archive_name = format!("kson-lib-{shared_or_static}-{cpu_arch}-{os}.tar.gz");
+
+    // Populate (or reuse) a shared cache entry, then hand its contents to
+    // OUT_DIR. In a multi-crate workspace or parallel CI this turns repeated
+    // builds from network-bound into near-instant.
+    let cache_key = format!("{KSON_LIB_VERSION}-{shared_or_static}-{cpu_arch}-{os}");
+    let cache_root = cache_root_dir();
+    let cache_dir = cache_root.join(&cache_key);
+    fs::create_dir_all(&cache_root)?;
+
+    // Serialize extraction across concurrent builders: the first to take the
+    // lock downloads, the rest wait and then reuse the cached artifacts.
+    let lock_path = cache_root.join(format!("{cache_key}.lock"));
+    let mut lock = fslock::LockFile::open(&lock_path)?;
+    lock.lock()?;
+
+    let marker = cache_dir.join(".extracted");
+    if !marker.exists() {
+        populate_cache(&cache_dir, &marker, shared_or_static, cpu_arch, &os, &archive_name)?;
+    }
+
+    copy_dir_contents(&cache_dir, out_dir)?;
+    lock.unlock()?;
+
+    Ok(())
+}
+
+/// Download, verify, and unpack a prebuilt archive into a fresh cache entry,
+/// marking it complete on success. Assumes the caller holds the cache lock.
+fn populate_cache(
+    cache_dir: &Path,
+    marker: &Path,
+    shared_or_static: &str,
+    cpu_arch: &str,
+    os: &str,
+    archive_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // `KSON_MIRROR` lets corporate/air-gapped builds point at an internal
+    // artifact store instead of the public GitHub release host.
+    let base_url = env::var("KSON_MIRROR")
+        .unwrap_or_else(|_| "https://github.com/kson-org/kson-binaries".to_string());
+    let base_url = base_url.trim_end_matches('/');
     let url = format!(
-        "https://github.com/kson-org/kson-binaries/releases/download/kson-lib-{KSON_LIB_VERSION}/kson-lib-{shared_or_static}-{cpu_arch}-{os}.tar.gz"
+        "{base_url}/releases/download/kson-lib-{KSON_LIB_VERSION}/{archive_name}"
     );
     let archive = ureq::get(url).call()?.body_mut().read_to_vec()?;
+
+    // Verify the download against the recorded digest before trusting its
+    // contents to the decompressor.
+    match expected_digest(shared_or_static, cpu_arch, os) {
+        Some(expected) => {
+            let actual = sha256_hex(&archive);
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(format!(
+                    "checksum mismatch for {archive_name}: expected {expected}, got {actual}"
+                )
+                .into());
+            }
+        }
+        None => {
+            println!(
+                "cargo:warning=no SHA-256 recorded for {archive_name} (kson-lib {KSON_LIB_VERSION}); skipping integrity check"
+            );
+        }
+    }
+
+    // Extract into a clean directory so a half-populated entry from a previous
+    // failed build can't be mistaken for a complete one.
+    if cache_dir.exists() {
+        fs::remove_dir_all(cache_dir)?;
+    }
+    fs::create_dir_all(cache_dir)?;
     let decoder = flate2::read::GzDecoder::new(archive.as_slice());
-    let mut archive = tar::Archive::new(decoder);
-    archive.unpack(out_dir)?;
+    let mut tar_archive = tar::Archive::new(decoder);
+    tar_archive.unpack(cache_dir)?;
 
+    fs::write(marker, KSON_LIB_VERSION)?;
     Ok(())
 }
 
+/// The shared cache root: `KSON_CACHE_DIR` if set, else `$CARGO_HOME/kson-cache`
+/// (falling back to `$HOME/.cargo`).
+fn cache_root_dir() -> PathBuf {
+    if let Ok(dir) = env::var("KSON_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    let cargo_home = env::var("CARGO_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".cargo")
+        });
+    cargo_home.join("kson-cache")
+}
+
+/// Copy every file from `src` into `dst`, hard-linking where possible and
+/// falling back to a byte copy (e.g. across filesystems). The `.extracted`
+/// marker is skipped.
+fn copy_dir_contents(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let source_path = entry.path();
+        if !source_path.is_file() {
+            continue;
+        }
+        let file_name = source_path.file_name().unwrap();
+        if file_name == ".extracted" {
+            continue;
+        }
+        let dest_path = dst.join(file_name);
+        let _ = fs::remove_file(&dest_path);
+        if fs::hard_link(&source_path, &dest_path).is_err() {
+            fs::copy(&source_path, &dest_path)?;
+        }
+    }
+    Ok(())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(bytes);
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
+
 fn build_kson_from_source(
     kson_root: &Path,
     use_dynamic_linking: bool,
@@ -127,29 +393,94 @@ fn build_kson_from_source(
     Ok(())
 }
 
+/// True for build environments that can neither reach the network nor link the
+/// native library: docs.rs (sandboxed) and RLS / rust-analyzer passes (which
+/// set `CARGO` to their own shim). Such builds consume the checked-in bindings.
+fn is_offline_build() -> bool {
+    if env::var_os("DOCS_RS").is_some() {
+        return true;
+    }
+    if let Ok(cargo) = env::var("CARGO") {
+        if let Some(stem) = Path::new(&cargo).file_stem().and_then(|stem| stem.to_str()) {
+            if stem == "rls" || stem == "rust-analyzer" {
+                return true;
+            }
+        }
+    }
+    false
+}
+
 fn main() {
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
     let use_dynamic_linking = cfg!(feature = "dynamic-linking") || cfg!(target_os = "windows");
 
-    // Obtain kotlin artifacts (TODO: allow compiling from source)
-    get_kson_artifacts(use_dynamic_linking, &out_dir).expect("Failed to copy Kotlin artifacts");
+    // Re-run whenever an env var that selects or sources the artifacts changes,
+    // so stale artifacts aren't silently reused.
+    for var in [
+        "KSON_STRATEGY",
+        "KSON_ROOT_SOURCE_DIR",
+        "KSON_PREBUILT_BIN_DIR",
+        "KSON_LIB_LOCATION",
+        "KSON_MIRROR",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
 
-    // Generate bindings
-    let bindings = bindgen::Builder::default()
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let prebuilt_bindings = manifest_dir.join("prebuilt-bindings.rs");
+    let regenerate_bindings = cfg!(feature = "generate-bindings");
+
+    // On docs.rs / RLS, skip artifact acquisition and native linking entirely
+    // and fall back to the bindings committed in the repo.
+    if is_offline_build() && !regenerate_bindings {
+        fs::copy(&prebuilt_bindings, out_dir.join("bindings.rs"))
+            .expect("offline build requires a checked-in prebuilt-bindings.rs");
+        return;
+    }
+
+    // Obtain kotlin artifacts according to the selected strategy
+    let strategy = resolve_strategy();
+    let layout = get_kson_artifacts(strategy, use_dynamic_linking, &out_dir)
+        .expect("Failed to obtain Kotlin artifacts");
+
+    // Generate bindings, filtered by the declarative allowlist in bindings.toml
+    let config = load_bindings_config(&manifest_dir);
+    let mut builder = bindgen::Builder::default()
         .header(
-            out_dir
+            layout
+                .header_dir
                 .join("kson_api_preprocessed.h")
                 .display()
                 .to_string(),
         )
-        .parse_callbacks(Box::new(CustomRenamer))
-        .generate()
-        .expect("Unable to generate bindings");
+        .parse_callbacks(Box::new(CustomRenamer));
+    for pattern in &config.types {
+        builder = builder.allowlist_type(pattern);
+    }
+    for pattern in &config.functions {
+        builder = builder.allowlist_function(pattern);
+    }
+    for pattern in &config.variables {
+        builder = builder.allowlist_var(pattern);
+    }
+    for pattern in &config.opaque {
+        builder = builder.opaque_type(pattern);
+    }
+    for pattern in &config.enums {
+        builder = builder.constified_enum_module(pattern);
+    }
+    let bindings = builder.generate().expect("Unable to generate bindings");
 
     bindings
         .write_to_file(out_dir.join("bindings.rs"))
         .expect("Couldn't write bindings!");
 
+    // Refresh the checked-in bindings consumed by offline builds.
+    if regenerate_bindings {
+        fs::copy(out_dir.join("bindings.rs"), &prebuilt_bindings)
+            .expect("Couldn't refresh prebuilt-bindings.rs");
+    }
+
     // Deal with static vs. dynamic linking
     if use_dynamic_linking {
         // Let users of the library know the path to the compiled binary, so they can copy it
@@ -163,8 +494,11 @@ fn main() {
         let built_lib = out_dir.join(&shared_name);
         println!("cargo:lib-binary={}", built_lib.display());
     } else {
-        // Tell the compiler where to find the static library
-        println!("cargo:rustc-link-search=native={}", out_dir.display());
+        // Tell the compiler where to find the static library. The `system`
+        // strategy already emitted a link-search for `KSON_LIB_LOCATION`.
+        if !layout.system {
+            println!("cargo:rustc-link-search=native={}", out_dir.display());
+        }
         println!("cargo:rustc-link-lib=static=kson");
 
         // Note: our kotlin-native binary relies on platform-specific libraries, which we don't want
@@ -0,0 +1,192 @@
+//! Terminal-style annotated diagnostic reports over `Message` lists.
+//!
+//! [`SchemaValidatorService::validate`](crate::SchemaValidatorService) and
+//! [`Failure::errors`](crate::result::Failure::errors) both hand back a flat
+//! `Vec<Message>`, each carrying a [`MessageSeverity`] and a `start`/`end`
+//! [`Position`] — but only `Debug` is available for display. [`Message::render`]
+//! (in [`render`](crate::render)) already draws a single snippet; this module
+//! renders a *whole list* at once in the spirit of `ariadne`/`rustc`: diagnostics
+//! are sorted by start position, each gets a `error:`/`warning:` header and a
+//! line-numbered gutter, and the offending columns are underlined with `^`/`~`
+//! carets. Multi-line spans underline the first line from `start.column`, fully
+//! underline interior lines, and stop at `end.column` on the last line, with a
+//! `|` connector drawn in the gutter between them. An ANSI flag colors the
+//! headers and carets by severity for terminals, or leaves them plain for
+//! captured logs.
+
+use crate::{Message, MessageSeverity, Position};
+
+/// Render `messages` as plain (un-colored) annotated snippets against `source`,
+/// suitable for captured logs and snapshot tests.
+pub fn render_diagnostics(source: &str, messages: &[Message]) -> String {
+    render(source, messages, false)
+}
+
+/// Render `messages` as ANSI-colored annotated snippets against `source`, with
+/// headers and carets colored by severity for terminal display.
+pub fn render_diagnostics_ansi(source: &str, messages: &[Message]) -> String {
+    render(source, messages, true)
+}
+
+const RESET: &str = "\u{1b}[0m";
+
+fn severity_color(severity: &MessageSeverity) -> &'static str {
+    match severity {
+        MessageSeverity::Error => "\u{1b}[31m",   // red
+        MessageSeverity::Warning => "\u{1b}[33m", // yellow
+    }
+}
+
+fn severity_label(severity: &MessageSeverity) -> &'static str {
+    match severity {
+        MessageSeverity::Error => "error",
+        MessageSeverity::Warning => "warning",
+    }
+}
+
+fn render(source: &str, messages: &[Message], ansi: bool) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+
+    // Sort a borrowed view by start position without disturbing the caller's
+    // ordering; JNI `Message` handles are cheap to reference but not to clone.
+    let mut order: Vec<usize> = (0..messages.len()).collect();
+    order.sort_by_key(|&i| {
+        let start = messages[i].start();
+        (start.line(), start.column())
+    });
+
+    order
+        .iter()
+        .map(|&i| render_one(&messages[i], &lines, ansi))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_one(message: &Message, lines: &[&str], ansi: bool) -> String {
+    let start = message.start();
+    let end = message.end();
+    let severity = message.severity();
+    let color = severity_color(&severity);
+
+    let mut out = String::new();
+    if ansi {
+        out.push_str(&format!(
+            "{color}{}{RESET}: {}\n",
+            severity_label(&severity),
+            message.message()
+        ));
+    } else {
+        out.push_str(&format!("{}: {}\n", severity_label(&severity), message.message()));
+    }
+    out.push_str(&format!("--> {}:{}\n", start.line(), start.column()));
+
+    // Skip the body when the span starts outside `source`.
+    let start_index = match line_index(start.line(), lines) {
+        Some(index) => index,
+        None => return out,
+    };
+    let end_index = line_index(end.line(), lines).unwrap_or(start_index);
+
+    let gutter_width = end.line().max(start.line()).max(1).to_string().len();
+    let border = format!("{:width$} |", "", width = gutter_width);
+    out.push_str(&border);
+    out.push('\n');
+
+    for index in start_index..=end_index {
+        let line = lines[index];
+        out.push_str(&format!(
+            "{:>width$} | {}\n",
+            index,
+            line,
+            width = gutter_width
+        ));
+
+        let (caret_start, caret_end) =
+            caret_bounds(index, start_index, end_index, start.column(), end.column(), line);
+
+        // Draw a `|` connector in the gutter for the blank lead-in of interior
+        // and trailing rows of a multi-line span.
+        if index > start_index {
+            out.push_str(&format!("{:width$} |", "", width = gutter_width));
+        } else {
+            out.push_str(&border);
+        }
+        out.push(' ');
+
+        let underline = underline_row(caret_start, caret_end);
+        if ansi {
+            out.push_str(color);
+            out.push_str(&underline);
+            out.push_str(RESET);
+        } else {
+            out.push_str(&underline);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Build an underline row: leading spaces up to `caret_start`, then a `^` under
+/// the first marked column and `~` under the rest.
+fn underline_row(caret_start: usize, caret_end: usize) -> String {
+    let mut row = String::new();
+    for _ in 0..caret_start {
+        row.push(' ');
+    }
+    let count = (caret_end - caret_start).max(1);
+    for offset in 0..count {
+        row.push(if offset == 0 { '^' } else { '~' });
+    }
+    row
+}
+
+/// Convert a 0-based [`Position`] line into an index into `lines`, returning
+/// `None` once it falls outside the source range.
+fn line_index(line: i32, lines: &[&str]) -> Option<usize> {
+    if line < 0 {
+        return None;
+    }
+    let index = line as usize;
+    (index < lines.len()).then_some(index)
+}
+
+/// Compute the `[start, end)` caret column range for `line`, clamped to the
+/// line's length. Interior and trailing lines of a multi-line span underline
+/// from column 0 and/or to end-of-line as appropriate.
+fn caret_bounds(
+    index: usize,
+    start_index: usize,
+    end_index: usize,
+    start_column: i32,
+    end_column: i32,
+    line: &str,
+) -> (usize, usize) {
+    let len = line.chars().count();
+    let clamp = |column: i32| (column.max(0) as usize).min(len);
+
+    let caret_start = if index == start_index { clamp(start_column) } else { 0 };
+    let caret_end = if index == end_index { clamp(end_column) } else { len };
+    (caret_start, caret_end.max(caret_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_index, underline_row};
+
+    #[test]
+    fn line_index_zero_based_bounds() {
+        let lines = ["one", "two", "three"];
+        assert_eq!(line_index(0, &lines), Some(0));
+        assert_eq!(line_index(2, &lines), Some(2));
+        assert_eq!(line_index(-1, &lines), None);
+        assert_eq!(line_index(3, &lines), None);
+    }
+
+    #[test]
+    fn underline_row_uses_caret_then_tildes() {
+        assert_eq!(underline_row(2, 6), "  ^~~~");
+        // A zero-width span still draws a single caret.
+        assert_eq!(underline_row(0, 0), "^");
+    }
+}
@@ -0,0 +1,324 @@
+//! Compact, self-describing binary encoding for [`KsonValue`].
+//!
+//! Re-tokenizing a large configuration on every start-up is wasteful when the
+//! document has not changed. This module adds a fast parse path: [`Kson::to_binary`]
+//! walks a parsed [`KsonValue`] tree into a byte buffer and [`Kson::from_binary`]
+//! reconstructs the tree without touching the text analyzer. The layout is a tag
+//! byte per node followed by length-prefixed payloads — varint lengths for
+//! collections and strings, IEEE-754 for decimals, and a zig-zag varint for
+//! integers. Source spans are persisted optionally so round-tripped values keep
+//! their `start()`/`end()` positions for downstream diagnostics.
+
+use crate::{kson_value, Kson, KsonValue, Position};
+
+/// An error produced while decoding a binary [`KsonValue`] buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended before a value could be fully decoded.
+    UnexpectedEof,
+    /// A node tag byte did not match any known variant.
+    UnknownTag(u8),
+    /// A length or varint did not fit the expected bounds.
+    Malformed(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnexpectedEof => f.write_str("unexpected end of binary kson buffer"),
+            Error::UnknownTag(tag) => write!(f, "unknown kson node tag: {tag}"),
+            Error::Malformed(msg) => write!(f, "malformed binary kson: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Node tags. Kept stable on the wire; append new variants rather than reordering.
+const TAG_NULL: u8 = 0;
+const TAG_BOOLEAN: u8 = 1;
+const TAG_INTEGER: u8 = 2;
+const TAG_DECIMAL: u8 = 3;
+const TAG_STRING: u8 = 4;
+const TAG_ARRAY: u8 = 5;
+const TAG_OBJECT: u8 = 6;
+const TAG_EMBED: u8 = 7;
+
+// Header flag bits.
+const FLAG_SPANS: u8 = 0b0000_0001;
+
+impl Kson {
+    /// Encode `value` into a compact binary buffer, persisting source spans so a
+    /// decoded tree keeps its `start()`/`end()` positions.
+    pub fn to_binary(value: &KsonValue) -> Vec<u8> {
+        Self::to_binary_with_options(value, true)
+    }
+
+    /// Encode `value`, choosing whether to persist source spans. Dropping spans
+    /// yields a smaller buffer when positions are not needed downstream.
+    pub fn to_binary_with_options(value: &KsonValue, retain_spans: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.push(if retain_spans { FLAG_SPANS } else { 0 });
+        write_value(value, retain_spans, &mut out);
+        out
+    }
+
+    /// Decode a buffer produced by [`Kson::to_binary`] back into a [`KsonValue`].
+    pub fn from_binary(bytes: &[u8]) -> Result<KsonValue, Error> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let flags = reader.read_byte()?;
+        let spans = flags & FLAG_SPANS != 0;
+        read_value(&mut reader, spans)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Encoding
+// ---------------------------------------------------------------------------
+
+fn write_value(value: &KsonValue, spans: bool, out: &mut Vec<u8>) {
+    match value {
+        KsonValue::KsonNull(n) => {
+            out.push(TAG_NULL);
+            write_span(spans, n.start(), n.end(), out);
+        }
+        KsonValue::KsonBoolean(b) => {
+            out.push(TAG_BOOLEAN);
+            write_span(spans, b.start(), b.end(), out);
+            out.push(u8::from(b.value()));
+        }
+        KsonValue::KsonString(s) => {
+            out.push(TAG_STRING);
+            write_span(spans, s.start(), s.end(), out);
+            write_str(&s.value(), out);
+        }
+        KsonValue::KsonNumber(n) => match n {
+            kson_value::KsonNumber::Integer(i) => {
+                out.push(TAG_INTEGER);
+                write_span(spans, i.start(), i.end(), out);
+                write_varint(zigzag(i.value() as i64), out);
+            }
+            kson_value::KsonNumber::Decimal(d) => {
+                out.push(TAG_DECIMAL);
+                write_span(spans, d.start(), d.end(), out);
+                out.extend_from_slice(&d.value().to_bits().to_le_bytes());
+            }
+        },
+        KsonValue::KsonArray(a) => {
+            out.push(TAG_ARRAY);
+            write_span(spans, a.start(), a.end(), out);
+            let elements = a.elements();
+            write_varint(elements.len() as u64, out);
+            for element in &elements {
+                write_value(element, spans, out);
+            }
+        }
+        KsonValue::KsonObject(o) => {
+            out.push(TAG_OBJECT);
+            write_span(spans, o.start(), o.end(), out);
+            let properties = o.properties();
+            write_varint(properties.len() as u64, out);
+            for (key, value) in &properties {
+                write_str(key, out);
+                write_value(value, spans, out);
+            }
+        }
+        KsonValue::KsonEmbed(e) => {
+            out.push(TAG_EMBED);
+            write_span(spans, e.start(), e.end(), out);
+            match e.tag() {
+                Some(tag) => {
+                    out.push(1);
+                    write_str(&tag, out);
+                }
+                None => out.push(0),
+            }
+            write_str(&e.content(), out);
+        }
+    }
+}
+
+fn write_span(spans: bool, start: Position, end: Position, out: &mut Vec<u8>) {
+    if !spans {
+        return;
+    }
+    write_varint(zigzag(start.line() as i64), out);
+    write_varint(zigzag(start.column() as i64), out);
+    write_varint(zigzag(end.line() as i64), out);
+    write_varint(zigzag(end.column() as i64), out);
+}
+
+fn write_str(s: &str, out: &mut Vec<u8>) {
+    write_varint(s.len() as u64, out);
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+// ---------------------------------------------------------------------------
+// Decoding
+// ---------------------------------------------------------------------------
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        let byte = *self.bytes.get(self.pos).ok_or(Error::UnexpectedEof)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self.pos.checked_add(len).ok_or(Error::UnexpectedEof)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(Error::UnexpectedEof)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut result = 0u64;
+        let mut shift = 0u32;
+        loop {
+            if shift >= 64 {
+                return Err(Error::Malformed("varint too long".to_string()));
+            }
+            let byte = self.read_byte()?;
+            result |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_str(&mut self) -> Result<String, Error> {
+        let len = self.read_varint()? as usize;
+        let slice = self.read_slice(len)?;
+        std::str::from_utf8(slice)
+            .map(str::to_string)
+            .map_err(|e| Error::Malformed(e.to_string()))
+    }
+}
+
+fn read_value(reader: &mut Reader<'_>, spans: bool) -> Result<KsonValue, Error> {
+    let tag = reader.read_byte()?;
+    let (start, end) = read_span(reader, spans)?;
+    match tag {
+        TAG_NULL => Ok(KsonValue::KsonNull(kson_value::KsonNull::new(start, end))),
+        TAG_BOOLEAN => {
+            let value = reader.read_byte()? != 0;
+            Ok(KsonValue::KsonBoolean(kson_value::KsonBoolean::new(
+                value, start, end,
+            )))
+        }
+        TAG_INTEGER => {
+            let value = unzigzag(reader.read_varint()?) as i32;
+            Ok(KsonValue::KsonNumber(kson_value::KsonNumber::Integer(
+                kson_value::kson_number::Integer::new(value, start, end),
+            )))
+        }
+        TAG_DECIMAL => {
+            let bits = u64::from_le_bytes(
+                reader
+                    .read_slice(8)?
+                    .try_into()
+                    .expect("read_slice returned 8 bytes"),
+            );
+            Ok(KsonValue::KsonNumber(kson_value::KsonNumber::Decimal(
+                kson_value::kson_number::Decimal::new(f64::from_bits(bits), start, end),
+            )))
+        }
+        TAG_STRING => {
+            let value = reader.read_str()?;
+            Ok(KsonValue::KsonString(kson_value::KsonString::new(
+                &value, start, end,
+            )))
+        }
+        TAG_ARRAY => {
+            let len = reader.read_varint()? as usize;
+            let mut elements = Vec::with_capacity(len);
+            for _ in 0..len {
+                elements.push(read_value(reader, spans)?);
+            }
+            Ok(KsonValue::KsonArray(kson_value::KsonArray::new(
+                &elements, start, end,
+            )))
+        }
+        TAG_OBJECT => {
+            let len = reader.read_varint()? as usize;
+            let mut keys = Vec::with_capacity(len);
+            let mut values = Vec::with_capacity(len);
+            for _ in 0..len {
+                keys.push(reader.read_str()?);
+                values.push(read_value(reader, spans)?);
+            }
+            let properties: std::collections::HashMap<&str, KsonValue> = keys
+                .iter()
+                .zip(values.iter())
+                .map(|(k, v)| (k.as_str(), v.clone()))
+                .collect();
+            let key_nodes: std::collections::HashMap<&str, kson_value::KsonString> = keys
+                .iter()
+                .map(|k| (k.as_str(), kson_value::KsonString::new(k, start, end)))
+                .collect();
+            Ok(KsonValue::KsonObject(kson_value::KsonObject::new(
+                &properties,
+                &key_nodes,
+                start,
+                end,
+            )))
+        }
+        TAG_EMBED => {
+            let tag = match reader.read_byte()? {
+                0 => None,
+                _ => Some(reader.read_str()?),
+            };
+            let content = reader.read_str()?;
+            Ok(KsonValue::KsonEmbed(kson_value::KsonEmbed::new(
+                tag.as_deref(),
+                &content,
+                start,
+                end,
+            )))
+        }
+        other => Err(Error::UnknownTag(other)),
+    }
+}
+
+fn read_span(reader: &mut Reader<'_>, spans: bool) -> Result<(Position, Position), Error> {
+    if !spans {
+        return Ok((Position::new(0, 0), Position::new(0, 0)));
+    }
+    let start = Position::new(
+        unzigzag(reader.read_varint()?) as i32,
+        unzigzag(reader.read_varint()?) as i32,
+    );
+    let end = Position::new(
+        unzigzag(reader.read_varint()?) as i32,
+        unzigzag(reader.read_varint()?) as i32,
+    );
+    Ok((start, end))
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
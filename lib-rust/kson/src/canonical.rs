@@ -0,0 +1,150 @@
+//! Deterministic, canonical formatting.
+//!
+//! The Kotlin formatter's [`FormattingStyle`](crate::FormattingStyle) offers
+//! only layout variants; none guarantee byte-stable output. This module adds a
+//! canonical printer that operates on a parsed [`KsonValue`] tree: object keys
+//! are sorted, numbers are normalized to a single representation (so `3E5` and
+//! `300000` print identically), and strings are consistently quoted. The result
+//! is idempotent — formatting canonical output again yields the same bytes —
+//! which makes KSON diffable in version control and safe to hash.
+
+use crate::{kson_value, IndentType, Kson, KsonValue};
+
+impl Kson {
+    /// Format `kson` into canonical, byte-stable KSON text using `indent` for
+    /// nesting. Returns the input unchanged if it does not parse to a value.
+    pub fn format_canonical(kson: &str, indent: &IndentType) -> String {
+        match Kson::analyze(kson, None).kson_value() {
+            Some(value) => canonical_string(&value, indent),
+            None => kson.to_string(),
+        }
+    }
+}
+
+impl KsonValue {
+    /// Render this value as canonical KSON text. Equivalent to
+    /// [`Kson::format_canonical`] applied to an already-parsed tree.
+    pub fn to_canonical_string(&self, indent: &IndentType) -> String {
+        canonical_string(self, indent)
+    }
+}
+
+fn canonical_string(value: &KsonValue, indent: &IndentType) -> String {
+    let mut out = String::new();
+    write_value(value, indent, 0, &mut out);
+    out.push('\n');
+    out
+}
+
+fn write_indent(indent: &IndentType, depth: usize, out: &mut String) {
+    match indent {
+        IndentType::Tabs(_) => {
+            for _ in 0..depth {
+                out.push('\t');
+            }
+        }
+        IndentType::Spaces(spaces) => {
+            let width = spaces.size().max(0) as usize;
+            for _ in 0..depth * width {
+                out.push(' ');
+            }
+        }
+    }
+}
+
+fn write_value(value: &KsonValue, indent: &IndentType, depth: usize, out: &mut String) {
+    match value {
+        KsonValue::KsonNull(_) => out.push_str("null"),
+        KsonValue::KsonBoolean(b) => out.push_str(if b.value() { "true" } else { "false" }),
+        KsonValue::KsonString(s) => write_quoted(&s.value(), out),
+        KsonValue::KsonEmbed(e) => write_embed(e, out),
+        KsonValue::KsonNumber(n) => out.push_str(&canonical_number(n)),
+        KsonValue::KsonArray(a) => {
+            let elements = a.elements();
+            if elements.is_empty() {
+                out.push_str("[]");
+                return;
+            }
+            out.push('[');
+            out.push('\n');
+            for (i, element) in elements.iter().enumerate() {
+                write_indent(indent, depth + 1, out);
+                write_value(element, indent, depth + 1, out);
+                if i + 1 < elements.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            write_indent(indent, depth, out);
+            out.push(']');
+        }
+        KsonValue::KsonObject(o) => {
+            let mut entries: Vec<(String, KsonValue)> = o.properties().into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            if entries.is_empty() {
+                out.push_str("{}");
+                return;
+            }
+            out.push('{');
+            out.push('\n');
+            for (i, (key, value)) in entries.iter().enumerate() {
+                write_indent(indent, depth + 1, out);
+                write_quoted(key, out);
+                out.push_str(": ");
+                write_value(value, indent, depth + 1, out);
+                if i + 1 < entries.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            write_indent(indent, depth, out);
+            out.push('}');
+        }
+    }
+}
+
+/// Normalize a number to a single canonical representation: integers print
+/// without a fractional part, and decimals use Rust's shortest round-tripping
+/// form (which normalizes `3E5` to `300000`).
+fn canonical_number(number: &kson_value::KsonNumber) -> String {
+    match number {
+        kson_value::KsonNumber::Integer(i) => i.value().to_string(),
+        // `f64`'s `Display` already renders integral values without a fractional
+        // part or exponent (`3E5` -> `300000`); a prior `value as i64` cast
+        // saturated anything above `i64::MAX`, so use `to_string` directly.
+        kson_value::KsonNumber::Decimal(d) => d.value().to_string(),
+    }
+}
+
+/// Write an embed block in its canonical delimited form — `%tag` (or bare `%`
+/// when untagged), the content, then a closing `%%` — so re-parsing the output
+/// reproduces the same [`KsonEmbed`](kson_value::KsonEmbed) rather than a
+/// flattened string.
+fn write_embed(embed: &kson_value::KsonEmbed, out: &mut String) {
+    out.push('%');
+    if let Some(tag) = embed.tag() {
+        out.push_str(&tag);
+    }
+    out.push('\n');
+    let content = embed.content();
+    out.push_str(&content);
+    if !content.is_empty() && !content.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str("%%");
+}
+
+fn write_quoted(s: &str, out: &mut String) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
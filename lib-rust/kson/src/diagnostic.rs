@@ -0,0 +1,193 @@
+//! Node-anchored diagnostics with caret-underlined source snippets.
+//!
+//! Every [`KsonValue`] variant exposes `start()`/`end()` [`Position`]s, but
+//! turning those spans into human-readable, underlined diagnostics is left to
+//! the caller. This module adds a [`SourceSpan`]/[`Diagnostic`] pair and a
+//! [`DiagnosticBuilder`] so validation code — a schema checker walking a
+//! `KsonArray`, say — can attach messages to specific nodes and emit one
+//! formatted report: a `error:`/`warning:` header, a `--> line:column` locator,
+//! and the offending source line(s) underlined with `^`.
+
+use crate::{KsonValue, MessageSeverity, Position};
+
+/// A half-open source range, as a start and end [`Position`].
+#[derive(Clone)]
+pub struct SourceSpan {
+    start: Position,
+    end: Position,
+}
+
+impl SourceSpan {
+    /// A span covering `start..end`.
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// The span of a node, taken from its `start()`/`end()` positions.
+    pub fn of(value: &KsonValue) -> Self {
+        Self {
+            start: value.start(),
+            end: value.end(),
+        }
+    }
+}
+
+/// A single diagnostic: a severity, a message, and the span it points at.
+pub struct Diagnostic {
+    pub severity: MessageSeverity,
+    pub message: String,
+    pub span: SourceSpan,
+}
+
+/// Accumulates [`Diagnostic`]s attached to nodes, then renders them as one
+/// report against the original source text.
+#[derive(Default)]
+pub struct DiagnosticBuilder {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticBuilder {
+    /// An empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach a diagnostic to an explicit span.
+    pub fn attach(
+        &mut self,
+        span: SourceSpan,
+        severity: MessageSeverity,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            message: message.into(),
+            span,
+        });
+        self
+    }
+
+    /// Attach an error to `node`.
+    pub fn error(&mut self, node: &KsonValue, message: impl Into<String>) -> &mut Self {
+        self.attach(SourceSpan::of(node), MessageSeverity::Error, message)
+    }
+
+    /// Attach a warning to `node`.
+    pub fn warning(&mut self, node: &KsonValue, message: impl Into<String>) -> &mut Self {
+        self.attach(SourceSpan::of(node), MessageSeverity::Warning, message)
+    }
+
+    /// Whether any diagnostics have been collected.
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The collected diagnostics.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Render every diagnostic as an underlined snippet, ordered by source
+    /// position.
+    pub fn render(&self, source: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut ordered: Vec<&Diagnostic> = self.diagnostics.iter().collect();
+        ordered.sort_by_key(|d| (d.span.start.line(), d.span.start.column()));
+        ordered
+            .iter()
+            .map(|d| render_diagnostic(d, &lines))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn severity_label(severity: &MessageSeverity) -> &'static str {
+    match severity {
+        MessageSeverity::Error => "error",
+        MessageSeverity::Warning => "warning",
+    }
+}
+
+fn render_diagnostic(diagnostic: &Diagnostic, lines: &[&str]) -> String {
+    let start = diagnostic.span.start.clone();
+    let end = diagnostic.span.end.clone();
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{}: {}\n",
+        severity_label(&diagnostic.severity),
+        diagnostic.message
+    ));
+    out.push_str(&format!("--> {}:{}\n", start.line(), start.column()));
+
+    let start_index = match line_index(start.line(), lines) {
+        Some(index) => index,
+        None => return out,
+    };
+    let end_index = line_index(end.line(), lines).unwrap_or(start_index);
+
+    let gutter_width = (end.line().max(start.line())).max(1).to_string().len();
+    let border = format!("{:width$} |", "", width = gutter_width);
+    out.push_str(&border);
+    out.push('\n');
+
+    for index in start_index..=end_index {
+        let line = lines[index];
+        out.push_str(&format!(
+            "{:>width$} | {}\n",
+            index,
+            line,
+            width = gutter_width
+        ));
+
+        let len = line.chars().count();
+        let clamp = |column: i32| (column.max(0) as usize).min(len);
+        let caret_start = if index == start_index {
+            clamp(start.column())
+        } else {
+            0
+        };
+        let caret_end = if index == end_index {
+            clamp(end.column())
+        } else {
+            len
+        }
+        .max(caret_start);
+
+        out.push_str(&border);
+        out.push(' ');
+        for _ in 0..caret_start {
+            out.push(' ');
+        }
+        for _ in 0..(caret_end - caret_start).max(1) {
+            out.push('^');
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Map a [`Position`]'s 0-based line onto an index into `lines`, yielding
+/// `None` for lines before or past the source.
+fn line_index(line: i32, lines: &[&str]) -> Option<usize> {
+    if line < 0 {
+        return None;
+    }
+    let index = line as usize;
+    (index < lines.len()).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::line_index;
+
+    #[test]
+    fn first_line_is_in_bounds() {
+        let lines = ["alpha", "beta"];
+        assert_eq!(line_index(0, &lines), Some(0));
+        assert_eq!(line_index(1, &lines), Some(1));
+        assert_eq!(line_index(-1, &lines), None);
+        assert_eq!(line_index(2, &lines), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Per-tag external formatters for embedded blocks.
+//!
+//! [`Kson::format`] reflows the surrounding Kson but leaves embedded content
+//! verbatim. This module adds a registry — in the same shape as
+//! [`EmbedHandlers`](crate::embed_handlers::EmbedHandlers) — that pairs an embed
+//! `tag` with an external formatter, analogous to how tree-sitter injection
+//! grammars hand an embedded region to another language. [`Kson::format_with_embed_formatters`]
+//! runs [`Kson::format`] first, then pretty-prints each tagged `EmbedContent`
+//! span with the matching formatter and re-indents the result to the block's
+//! column before splicing it back. A formatter that errors leaves the original
+//! content untouched and contributes a warning [`Message`] to the result rather
+//! than aborting the whole format.
+
+use std::collections::HashMap;
+
+use crate::{FormatOptions, Kson, Message, MessageSeverity, TokenType};
+
+/// A formatter turns an embed block's raw content into a pretty-printed string,
+/// or an error message explaining why it could not.
+pub type EmbedFormatter = Box<dyn Fn(&str) -> Result<String, String>>;
+
+/// A registry of embed formatters keyed by tag name.
+#[derive(Default)]
+pub struct EmbedFormatters {
+    formatters: HashMap<String, EmbedFormatter>,
+}
+
+impl EmbedFormatters {
+    /// An empty registry; untagged blocks and tags without a formatter are left
+    /// verbatim.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a formatter for `tag`, replacing any previous entry.
+    pub fn register(&mut self, tag: &str, formatter: impl Fn(&str) -> Result<String, String> + 'static) {
+        self.formatters.insert(tag.to_string(), Box::new(formatter));
+    }
+
+    /// The formatter registered for `tag`, if any.
+    pub fn formatter(&self, tag: &str) -> Option<&EmbedFormatter> {
+        self.formatters.get(tag)
+    }
+}
+
+/// The output of [`Kson::format_with_embed_formatters`]: the formatted document
+/// and any warnings raised while running embed formatters.
+pub struct EmbedFormatResult {
+    output: String,
+    messages: Vec<Message>,
+}
+
+impl EmbedFormatResult {
+    /// The formatted document, with embed blocks pretty-printed where a
+    /// formatter succeeded.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// Warnings raised by formatters that failed; their blocks were left as-is.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+}
+
+impl Kson {
+    /// Format `kson`, then pretty-print each tagged embed block with the
+    /// matching formatter from `formatters`. See the [module docs](self).
+    pub fn format_with_embed_formatters(
+        kson: &str,
+        format_options: FormatOptions,
+        formatters: &EmbedFormatters,
+    ) -> EmbedFormatResult {
+        let output = Kson::format(kson, format_options);
+        let analysis = Kson::analyze(&output, None);
+
+        let mut messages = Vec::new();
+        // Collect replacements first, then apply them back-to-front so earlier
+        // byte offsets stay valid.
+        let mut replacements: Vec<(usize, usize, String)> = Vec::new();
+        let mut current_tag: Option<String> = None;
+
+        for token in analysis.tokens() {
+            match token.token_type() {
+                TokenType::EmbedTag => current_tag = Some(token.text()),
+                TokenType::EmbedContent => {
+                    let tag = current_tag.take();
+                    let Some(tag) = tag else { continue };
+                    let Some(formatter) = formatters.formatter(&tag) else { continue };
+
+                    let content = token.text();
+                    let start = token.start();
+                    match formatter(&content) {
+                        Ok(formatted) => {
+                            let reindented = reindent(&formatted, start.column());
+                            let begin = byte_offset(&output, start.line(), start.column());
+                            let end = begin + content.len();
+                            replacements.push((begin, end, reindented));
+                        }
+                        Err(reason) => {
+                            messages.push(Message::new(
+                                &format!("embed formatter for `{tag}` failed: {reason}"),
+                                MessageSeverity::Warning,
+                                start,
+                                token.end(),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut output = output;
+        replacements.sort_by(|a, b| b.0.cmp(&a.0));
+        for (begin, end, text) in replacements {
+            if begin <= end && end <= output.len() {
+                output.replace_range(begin..end, &text);
+            }
+        }
+
+        EmbedFormatResult { output, messages }
+    }
+}
+
+/// Re-indent `content` so that every line after the first is prefixed with
+/// `column` spaces, matching the embed block's indentation.
+fn reindent(content: &str, column: i32) -> String {
+    let indent = " ".repeat(column.max(0) as usize);
+    let mut lines = content.split('\n');
+    let mut out = lines.next().unwrap_or("").to_string();
+    for line in lines {
+        out.push('\n');
+        if !line.is_empty() {
+            out.push_str(&indent);
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Byte offset of the 0-based `(line, column)` char position within `source`.
+fn byte_offset(source: &str, line: i32, column: i32) -> usize {
+    let line = line.max(0) as usize;
+    let column = column.max(0) as usize;
+    let mut offset = 0;
+    for (index, text) in source.split('\n').enumerate() {
+        if index == line {
+            let in_line = text
+                .char_indices()
+                .nth(column)
+                .map(|(byte, _)| byte)
+                .unwrap_or(text.len());
+            return offset + in_line;
+        }
+        offset += text.len() + 1; // account for the '\n'
+    }
+    source.len()
+}
@@ -0,0 +1,110 @@
+//! Pluggable embed-tag handlers.
+//!
+//! Out of the box every `$embed` block is opaque text reachable through
+//! [`KsonValue::KsonEmbed`]'s `tag()`/`content()`. This module adds a registry
+//! mapping a tag name to a sub-parser so that, for example, an embed tagged
+//! `kson`/`json`/`yaml` is recursively parsed into a nested [`KsonValue`], while
+//! an unknown or plain-text tag stays a string.
+
+use std::collections::HashMap;
+
+use crate::{kson_value, Kson, KsonValue, Position};
+
+/// The result of running an embed handler over a block's content.
+#[derive(Debug, Clone)]
+pub enum DecodedEmbed {
+    /// The content was left as-is (no handler, or a text handler).
+    Raw(String),
+    /// The content was recursively parsed into a nested value.
+    Parsed(KsonValue),
+}
+
+/// A handler turns an embed block's raw content into a [`DecodedEmbed`].
+pub type EmbedHandler = Box<dyn Fn(&str) -> Result<DecodedEmbed, String>>;
+
+/// A registry of embed handlers keyed by tag name.
+#[derive(Default)]
+pub struct EmbedHandlers {
+    handlers: HashMap<String, EmbedHandler>,
+}
+
+impl EmbedHandlers {
+    /// An empty registry; unknown tags decode to [`DecodedEmbed::Raw`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry pre-populated with the built-in `kson`, `json` and `yaml`
+    /// handlers, each of which recursively parses the embedded content.
+    pub fn with_builtins() -> Self {
+        let mut handlers = Self::new();
+        for tag in ["kson", "json", "yaml"] {
+            handlers.register(tag, |content| {
+                let analysis = Kson::analyze(content, None);
+                match analysis.kson_value() {
+                    Some(value) => Ok(DecodedEmbed::Parsed(value)),
+                    None => Err("embedded content did not parse to a value".to_string()),
+                }
+            });
+        }
+        handlers
+    }
+
+    /// Register a handler for `tag`, replacing any previous entry.
+    pub fn register(
+        &mut self,
+        tag: &str,
+        handler: impl Fn(&str) -> Result<DecodedEmbed, String> + 'static,
+    ) {
+        self.handlers.insert(tag.to_string(), Box::new(handler));
+    }
+
+    /// Decode a single embed, dispatching on its tag. Unknown tags and handler
+    /// errors fall back to the raw content.
+    pub fn decode(&self, embed: &kson_value::KsonEmbed) -> DecodedEmbed {
+        let content = embed.content();
+        match embed.tag().and_then(|t| self.handlers.get(&t)) {
+            Some(handler) => handler(&content).unwrap_or(DecodedEmbed::Raw(content)),
+            None => DecodedEmbed::Raw(content),
+        }
+    }
+}
+
+impl Kson {
+    /// Analyze `kson` and decode every embed block using `handlers`, returning
+    /// the decoded embeds together with the source [`Position`] they start at so
+    /// diagnostics can point back into the outer document.
+    pub fn analyze_with_handlers(
+        kson: &str,
+        filepath: Option<&str>,
+        handlers: &EmbedHandlers,
+    ) -> Vec<(Position, DecodedEmbed)> {
+        let analysis = Kson::analyze(kson, filepath);
+        let mut decoded = Vec::new();
+        if let Some(value) = analysis.kson_value() {
+            collect_embeds(&value, handlers, &mut decoded);
+        }
+        decoded
+    }
+}
+
+fn collect_embeds(
+    value: &KsonValue,
+    handlers: &EmbedHandlers,
+    out: &mut Vec<(Position, DecodedEmbed)>,
+) {
+    match value {
+        KsonValue::KsonEmbed(embed) => out.push((embed.start(), handlers.decode(embed))),
+        KsonValue::KsonArray(array) => {
+            for element in array.elements() {
+                collect_embeds(&element, handlers, out);
+            }
+        }
+        KsonValue::KsonObject(object) => {
+            for value in object.properties().values() {
+                collect_embeds(value, handlers, out);
+            }
+        }
+        _ => {}
+    }
+}
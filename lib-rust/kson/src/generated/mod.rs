@@ -10,6 +10,9 @@ use std::ffi::c_int;
 use self::sys::jobject;
 use self::util::{AsKotlinObject, FromKotlinObject, KotlinPtr, ToKotlinObject};
 
+pub use self::util::KsonJniError;
+pub use self::util::{attach_as_daemon, init_jvm, JvmConfig, JvmInitError};
+
 
 #[derive(Clone)]
 pub enum SchemaResult {
@@ -2597,6 +2600,7 @@ impl std::hash::Hash for SchemaValidatorService {
 pub enum TranspileOptions {
     Json(transpile_options::Json),
     Yaml(transpile_options::Yaml),
+    Toml(transpile_options::Toml),
 }
 
 pub mod transpile_options {
@@ -2790,12 +2794,107 @@ pub mod transpile_options {
             util::apply_hash_code(self.to_kotlin_object(), state)
         }
     }
+
+
+    #[derive(Clone)]
+    pub struct Toml {
+        kotlin_ptr: KotlinPtr,
+    }
+
+    impl FromKotlinObject for Toml {
+        fn from_kotlin_object(obj: self::sys::jobject) -> Self {
+            let (env, _detach_guard) = util::attach_thread_to_java_vm();
+            let kotlin_ptr = util::to_gc_global_ref(env, obj);
+            Self { kotlin_ptr }
+        }
+    }
+
+    impl ToKotlinObject for Toml {
+        fn to_kotlin_object(&self) -> KotlinPtr {
+            self.kotlin_ptr.clone()
+        }
+    }
+
+    impl AsKotlinObject for Toml {
+        fn as_kotlin_object(&self) -> self::sys::jobject {
+            self.kotlin_ptr.inner.inner
+        }
+    }
+
+    impl Toml {
+        pub fn new(
+            retain_embed_tags: bool,
+        ) -> Self {
+            let (env, _detach_guard) = util::attach_thread_to_java_vm();
+            let class = util::get_class(env, c"org/kson/api/TranspileOptions$Toml");
+            let constructor = util::get_method(env, class.as_kotlin_object(), c"<init>", c"(Z)V");
+
+            let retain_embed_tags = retain_embed_tags as c_int;
+
+            let jobject = unsafe { (**env).NewObject.unwrap()(env, class.as_kotlin_object(), constructor,
+                retain_embed_tags,
+            )};
+            util::panic_upon_exception(env);
+            Self {
+                kotlin_ptr: util::to_gc_global_ref(env, jobject)
+            }
+        }
+    }
+
+
+    impl Toml {
+
+
+        pub fn retain_embed_tags(
+            &self,
+        ) -> bool {
+            let self_ptr = self.to_kotlin_object();
+            let self_obj = self_ptr.as_kotlin_object();
+
+
+            let (_, _detach_guard) = util::attach_thread_to_java_vm();
+            let result = call_jvm_function!(
+                util,
+                c"org/kson/api/TranspileOptions$Toml",
+                c"getRetainEmbedTags",
+                c"()Z",
+                CallBooleanMethod,
+                self_obj,
+
+            );
+
+            result != 0
+        }
+    }
+
+    impl std::fmt::Debug for Toml {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let obj = self.to_kotlin_object();
+            write!(f, "{}", util::call_to_string(c"org/kson/api/TranspileOptions$Toml", &obj))
+        }
+    }
+
+    impl Eq for Toml {}
+    impl PartialEq for Toml {
+        fn eq(&self, other: &Toml) -> bool {
+            util::equals(self.to_kotlin_object(), other.to_kotlin_object())
+        }
+    }
+    impl std::hash::Hash for Toml {
+        fn hash<H>(&self, state: &mut H)
+        where
+            H: std::hash::Hasher,
+        {
+            util::apply_hash_code(self.to_kotlin_object(), state)
+        }
+    }
 }
 impl FromKotlinObject for TranspileOptions {
     fn from_kotlin_object(obj: jobject) -> Self {
         match util::class_name(obj).as_str() {
             "org.kson.api.TranspileOptions$Json" => TranspileOptions::Json(transpile_options::Json::from_kotlin_object(obj)),
             "org.kson.api.TranspileOptions$Yaml" => TranspileOptions::Yaml(transpile_options::Yaml::from_kotlin_object(obj)),
+            "org.kson.api.TranspileOptions$Toml" => TranspileOptions::Toml(transpile_options::Toml::from_kotlin_object(obj)),
             _ => unreachable!(),
         }
     }
@@ -2806,6 +2905,7 @@ impl ToKotlinObject for TranspileOptions {
         match self {
             Self::Json(inner) => inner.to_kotlin_object(),
             Self::Yaml(inner) => inner.to_kotlin_object(),
+            Self::Toml(inner) => inner.to_kotlin_object(),
         }
     }
 }
@@ -3774,6 +3874,34 @@ impl Kson {
         FromKotlinObject::from_kotlin_object(result)
     }
 
+    /// Like [`Kson::format`], but surfaces a Java exception thrown by the
+    /// underlying call as [`KsonJniError`] instead of aborting the process.
+    pub fn try_format(
+        kson: &str,
+        format_options: FormatOptions,
+    ) -> std::result::Result<String, KsonJniError> {
+        let self_ptr = util::access_static_field(c"org/kson/Kson", c"INSTANCE", c"Lorg/kson/Kson;");
+        let self_obj = self_ptr.as_kotlin_object();
+        let kson_ptr = kson.to_kotlin_object();
+        let kson = kson_ptr.as_kotlin_object();
+        let format_options_ptr = format_options.to_kotlin_object();
+        let format_options = format_options_ptr.as_kotlin_object();
+
+        let (_, _detach_guard) = util::attach_thread_to_java_vm();
+        let result = try_call_jvm_function!(
+            util,
+            c"org/kson/Kson",
+            c"format",
+            c"(Ljava/lang/String;Lorg/kson/api/FormatOptions;)Ljava/lang/String;",
+            CallObjectMethod,
+            self_obj,
+            kson,
+            format_options,
+        )?;
+
+        Ok(FromKotlinObject::from_kotlin_object(result))
+    }
+
     /// Converts Kson to Json.
     ///
     /// @param kson The Kson source to convert
@@ -3836,6 +3964,37 @@ impl Kson {
         crate::kson_result_into_rust_result(FromKotlinObject::from_kotlin_object(result))
     }
 
+    /// Converts Kson to Toml.
+    ///
+    /// @param kson The Kson source to convert
+    /// @param options Options for the TOML transpilation
+    /// @return A Result containing either the Toml output or error messages
+    pub fn to_toml(
+        kson: &str,
+        options: transpile_options::Toml,
+    ) -> std::result::Result<result::Success, result::Failure> {
+        let self_ptr = util::access_static_field(c"org/kson/Kson", c"INSTANCE", c"Lorg/kson/Kson;");
+        let self_obj = self_ptr.as_kotlin_object();
+        let kson_ptr = kson.to_kotlin_object();
+        let kson = kson_ptr.as_kotlin_object();
+        let options_ptr = options.to_kotlin_object();
+        let options = options_ptr.as_kotlin_object();
+
+        let (_, _detach_guard) = util::attach_thread_to_java_vm();
+        let result = call_jvm_function!(
+            util,
+            c"org/kson/Kson",
+            c"toToml",
+            c"(Ljava/lang/String;Lorg/kson/api/TranspileOptions$Toml;)Lorg/kson/api/Result;",
+            CallObjectMethod,
+            self_obj,
+            kson,
+            options,
+        );
+
+        crate::kson_result_into_rust_result(FromKotlinObject::from_kotlin_object(result))
+    }
+
     /// Statically analyze the given Kson and return an [Analysis] object containing any messages generated along with a
     /// tokenized version of the source.  Useful for tooling/editor support.
     /// @param kson The Kson source to analyze
@@ -1,8 +1,10 @@
 #![allow(unused_variables)]
 
 use std::cell::RefCell;
-use std::ffi::CStr;
-use std::sync::{Arc, LazyLock};
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 
 use super::sys::*;
 
@@ -53,13 +55,105 @@ impl ToKotlinObject for KotlinPtr {
     }
 }
 
+/// Startup options for the embedded JVM, applied the first time a Kson API
+/// forces the VM to be created. Because the VM is built lazily, these must be
+/// registered via [`init_jvm`] *before* any Kson call.
+#[derive(Debug, Default, Clone)]
+pub struct JvmConfig {
+    options: Vec<String>,
+}
+
+impl JvmConfig {
+    /// An empty configuration, equivalent to the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `-Djava.class.path=<path>`.
+    pub fn classpath(mut self, path: &str) -> Self {
+        self.options.push(format!("-Djava.class.path={path}"));
+        self
+    }
+
+    /// Set the maximum heap size, e.g. `"512m"` for `-Xmx512m`.
+    pub fn max_heap(mut self, size: &str) -> Self {
+        self.options.push(format!("-Xmx{size}"));
+        self
+    }
+
+    /// Set a `-D<key>=<value>` system property.
+    pub fn property(mut self, key: &str, value: &str) -> Self {
+        self.options.push(format!("-D{key}={value}"));
+        self
+    }
+
+    /// Append a raw JVM option verbatim, for anything the typed builders above
+    /// don't cover.
+    pub fn option(mut self, option: &str) -> Self {
+        self.options.push(option.to_string());
+        self
+    }
+}
+
+/// Error returned by [`init_jvm`] when the VM can no longer be configured.
+#[derive(Debug, Clone)]
+pub enum JvmInitError {
+    /// The JVM had already been created by an earlier Kson call, so its
+    /// startup options are now fixed.
+    AlreadyInitialized,
+}
+
+impl std::fmt::Display for JvmInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JvmInitError::AlreadyInitialized => {
+                write!(f, "the JVM has already been initialized")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JvmInitError {}
+
+static JVM_CONFIG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static VM_CREATED: AtomicBool = AtomicBool::new(false);
+
+/// Register JVM startup options. Must be called before any other Kson API, as
+/// the VM is created lazily on first use; returns
+/// [`JvmInitError::AlreadyInitialized`] once the VM exists.
+pub fn init_jvm(config: JvmConfig) -> std::result::Result<(), JvmInitError> {
+    let mut stored = JVM_CONFIG.lock().unwrap();
+    if VM_CREATED.load(Ordering::SeqCst) {
+        return Err(JvmInitError::AlreadyInitialized);
+    }
+    *stored = config.options;
+    Ok(())
+}
+
 static JVM: LazyLock<Jvm> = LazyLock::new(|| {
+    let option_strings = JVM_CONFIG.lock().unwrap().clone();
+    let c_options: Vec<CString> = option_strings
+        .iter()
+        .map(|option| CString::new(option.as_str()).expect("JVM option contained a NUL byte"))
+        .collect();
+    let mut java_options: Vec<JavaVMOption> = c_options
+        .iter()
+        .map(|option| JavaVMOption {
+            optionString: option.as_ptr() as *mut _,
+            extraInfo: std::ptr::null_mut(),
+        })
+        .collect();
+
     let mut jvm = std::ptr::null_mut();
     let mut env = std::ptr::null_mut();
     let mut args = JavaVMInitArgs {
         version: 0x00010008, // JNI_VERSION_1_8
-        nOptions: 0,
-        options: std::ptr::null_mut(),
+        nOptions: java_options.len() as i32,
+        options: if java_options.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            java_options.as_mut_ptr()
+        },
         ignoreUnrecognized: 1
     };
 
@@ -67,6 +161,7 @@ static JVM: LazyLock<Jvm> = LazyLock::new(|| {
         panic!("failed to load JNI");
     }
 
+    VM_CREATED.store(true, Ordering::SeqCst);
     Jvm(jvm)
 });
 
@@ -77,8 +172,8 @@ thread_local! {
 macro_rules! call_jvm_function {
     ($util:ident, $class_name:expr, $method_name:expr, $method_signature:expr, $call_fn:ident, $obj:expr $(, $arg:expr )* $(,)?) => {{
         let (env, _detach_guard) = $util::attach_thread_to_java_vm();
-        let class = $util::get_class(env, $class_name);
-        let method = $util::get_method(env, class.as_kotlin_object(), $method_name, $method_signature);
+        let class = $util::cached_class(env, $class_name);
+        let method = $util::cached_method(env, class, $class_name, $method_name, $method_signature);
         let result = unsafe { (**env).$call_fn.unwrap()(env, $obj, method,
             $($arg, )*
         )};
@@ -87,6 +182,105 @@ macro_rules! call_jvm_function {
     }};
 }
 
+/// Fallible counterpart to [`call_jvm_function!`]: performs the same class /
+/// method lookup and call, but on a pending Java exception returns
+/// `Err(KsonJniError)` instead of panicking. Evaluates to
+/// `Result<_, KsonJniError>`.
+macro_rules! try_call_jvm_function {
+    ($util:ident, $class_name:expr, $method_name:expr, $method_signature:expr, $call_fn:ident, $obj:expr $(, $arg:expr )* $(,)?) => {{
+        let (env, _detach_guard) = $util::attach_thread_to_java_vm();
+        let class = $util::cached_class(env, $class_name);
+        let method = $util::cached_method(env, class, $class_name, $method_name, $method_signature);
+        let result = unsafe { (**env).$call_fn.unwrap()(env, $obj, method,
+            $($arg, )*
+        )};
+        $util::check_exception(env).map(|()| result)
+    }};
+}
+
+/// Generate a Kotlin-object binding for a data class with multiple accessible
+/// fields, eliminating the hand-written getter/constructor boilerplate that the
+/// generated structs otherwise repeat. Given the backing JVM class, a
+/// constructor signature, and one line per field (Rust name, Rust type, Kotlin
+/// getter name, getter signature), it emits the standard `kotlin_ptr` wrapper
+/// with `FromKotlinObject`/`ToKotlinObject`/`AsKotlinObject`, a `new`
+/// constructor invoking `NewObject`, and an object-returning accessor per field
+/// routed through [`call_jvm_function!`]:
+///
+/// ```ignore
+/// declare_kotlin_struct! {
+///     Message(c"org/kson/api/Message") {
+///         new c"(Ljava/lang/String;Lorg/kson/api/Position;)V" {
+///             message: &str,
+///             start: &Position,
+///         }
+///         message: String => c"getMessage", c"()Ljava/lang/String;";
+///         start: Position => c"getStart", c"()Lorg/kson/api/Position;";
+///     }
+/// }
+/// ```
+#[allow(unused_macros)]
+macro_rules! declare_kotlin_struct {
+    (
+        $(#[doc = $doc:literal])*
+        $name:ident($class:expr) {
+            new $ctor_sig:expr { $( $carg:ident : $carg_ty:ty ),* $(,)? }
+            $(
+                $field:ident : $field_ty:ty => $getter:expr, $getter_sig:expr ;
+            )*
+        }
+    ) => {
+        $(#[doc = $doc])*
+        pub struct $name {
+            kotlin_ptr: KotlinPtr,
+        }
+
+        impl FromKotlinObject for $name {
+            fn from_kotlin_object(obj: self::sys::jobject) -> Self {
+                let (env, _detach_guard) = util::attach_thread_to_java_vm();
+                let kotlin_ptr = util::to_gc_global_ref(env, obj);
+                Self { kotlin_ptr }
+            }
+        }
+
+        impl ToKotlinObject for $name {
+            fn to_kotlin_object(&self) -> KotlinPtr {
+                self.kotlin_ptr.clone()
+            }
+        }
+
+        impl AsKotlinObject for $name {
+            fn as_kotlin_object(&self) -> self::sys::jobject {
+                self.kotlin_ptr.inner.inner
+            }
+        }
+
+        impl $name {
+            pub fn new($( $carg: $carg_ty ),*) -> Self {
+                let (env, _detach_guard) = util::attach_thread_to_java_vm();
+                let class = util::get_class(env, $class);
+                let constructor = util::get_method(env, class.as_kotlin_object(), c"<init>", $ctor_sig);
+                $( let $carg = $carg.to_kotlin_object(); )*
+                let obj = unsafe { (**env).NewObject.unwrap()(env, class.as_kotlin_object(), constructor,
+                    $( $carg.as_kotlin_object(), )*
+                )};
+                util::panic_upon_exception(env);
+                Self { kotlin_ptr: util::to_gc_global_ref(env, obj) }
+            }
+
+            $(
+                pub fn $field(&self) -> $field_ty {
+                    let self_ptr = self.to_kotlin_object();
+                    let self_obj = self_ptr.as_kotlin_object();
+                    let (_, _detach_guard) = util::attach_thread_to_java_vm();
+                    let result = call_jvm_function!(util, $class, $getter, $getter_sig, CallObjectMethod, self_obj);
+                    FromKotlinObject::from_kotlin_object(result)
+                }
+            )*
+        }
+    };
+}
+
 pub struct DetachGuard {
     should_detach: bool
 }
@@ -117,6 +311,29 @@ pub(super) fn attach_thread_to_java_vm() -> (*mut JNIEnv, DetachGuard) {
     (env, DetachGuard { should_detach: true })
 }
 
+/// Attach the current thread to the JVM as a *daemon* and cache its `JNIEnv`
+/// for the thread's entire lifetime, never auto-detaching. Subsequent
+/// [`attach_thread_to_java_vm`] calls on this thread reuse the cached env and
+/// install a no-op [`DetachGuard`], so a worker making many short Kson calls
+/// pays the attach cost only once. This is an explicit opt-in; transient
+/// threads that don't call it keep the default RAII detach-on-drop behavior.
+pub fn attach_as_daemon() {
+    if ATTACHED_JNI_ENV.with_borrow(|maybe_env| maybe_env.is_some()) {
+        return;
+    }
+
+    let mut env = std::ptr::null_mut();
+    let attach_result = unsafe {
+        let attach = (**JVM.0).AttachCurrentThreadAsDaemon.unwrap();
+        attach(JVM.0, &mut env as *mut _ as *mut _, std::ptr::null_mut())
+    };
+    if attach_result != 0 {
+        panic!("failed to attach current thread to JNI as daemon");
+    }
+
+    ATTACHED_JNI_ENV.with_borrow_mut(|maybe_env| *maybe_env = Some(env));
+}
+
 pub(super) fn detach_thread_from_java_vm() {
     let detach_result = unsafe {
         let detach = (**JVM.0).DetachCurrentThread.unwrap();
@@ -179,6 +396,66 @@ pub(super) fn access_static_field(class_name: &CStr, field_name: &CStr, field_si
     to_gc_global_ref(env, field_value)
 }
 
+/// A resolved class global ref, safe to share: JNI global refs live for the
+/// program's lifetime and are not tied to any thread.
+struct CachedClass(jobject);
+unsafe impl Send for CachedClass {}
+
+/// A resolved `jmethodID`. Method IDs are stable for the lifetime of a loaded
+/// class, so caching and sharing one across threads is sound.
+struct CachedMethod(jmethodID);
+unsafe impl Send for CachedMethod {}
+
+static CLASS_CACHE: LazyLock<Mutex<HashMap<Vec<u8>, CachedClass>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static METHOD_CACHE: LazyLock<Mutex<HashMap<(Vec<u8>, Vec<u8>, Vec<u8>), CachedMethod>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Resolve `name` to a class global ref, caching it on first use. The global
+/// ref is kept alive for the whole program (intentionally leaked out of its
+/// [`OwnedKotlinPtr`]), which is why subsequent calls can reuse the raw
+/// `jobject` without risking a dangling reference.
+pub(super) fn cached_class(env: *mut JNIEnv, name: &CStr) -> jobject {
+    let key = name.to_bytes();
+    let mut cache = CLASS_CACHE.lock().unwrap();
+    if let Some(cached) = cache.get(key) {
+        return cached.0;
+    }
+
+    let owned = get_class(env, name);
+    let raw = owned.inner;
+    // Keep the global ref alive for the program's lifetime instead of deleting
+    // it when `owned` drops.
+    std::mem::forget(owned);
+    cache.insert(key.to_vec(), CachedClass(raw));
+    raw
+}
+
+/// Resolve a `jmethodID`, caching it keyed by `(class, name, signature)`. Must
+/// be called with the `class` returned by [`cached_class`] for the same
+/// `class_name`.
+pub(super) fn cached_method(
+    env: *mut JNIEnv,
+    class: jobject,
+    class_name: &CStr,
+    name: &CStr,
+    signature: &CStr,
+) -> jmethodID {
+    let key = (
+        class_name.to_bytes().to_vec(),
+        name.to_bytes().to_vec(),
+        signature.to_bytes().to_vec(),
+    );
+    let mut cache = METHOD_CACHE.lock().unwrap();
+    if let Some(cached) = cache.get(&key) {
+        return cached.0;
+    }
+
+    let method = get_method(env, class, name, signature);
+    cache.insert(key, CachedMethod(method));
+    method
+}
+
 pub(super) fn get_method(env: *mut JNIEnv, class: jobject, name: &CStr, signature: &CStr) -> jmethodID {
     let method_id = unsafe {
         let get_method_id = (**env).GetMethodID.unwrap();
@@ -188,6 +465,57 @@ pub(super) fn get_method(env: *mut JNIEnv, class: jobject, name: &CStr, signatur
     method_id
 }
 
+/// A Java exception that propagated out of a JNI call, captured as owned Rust
+/// data so it can cross back over the FFI boundary as an `Err`.
+#[derive(Debug, Clone)]
+pub struct KsonJniError {
+    /// Fully-qualified class name of the thrown `Throwable`.
+    pub class: String,
+    /// The throwable's `getMessage()`, or an empty string if it had none.
+    pub message: String,
+}
+
+impl std::fmt::Display for KsonJniError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.class)
+        } else {
+            write!(f, "{}: {}", self.class, self.message)
+        }
+    }
+}
+
+impl std::error::Error for KsonJniError {}
+
+/// Check for a pending Java exception after a JNI call. If one is set, capture
+/// its class and message, clear it, and return `Err`; otherwise `Ok(())`. This
+/// is the fallible counterpart to [`panic_upon_exception`].
+pub(super) fn check_exception(env: *mut JNIEnv) -> std::result::Result<(), KsonJniError> {
+    let has_exception = unsafe {
+        let exception_check = (**env).ExceptionCheck.unwrap();
+        exception_check(env) == 1
+    };
+
+    if !has_exception {
+        return Ok(());
+    }
+
+    let throwable = unsafe {
+        let exception_occurred = (**env).ExceptionOccurred.unwrap();
+        let throwable = exception_occurred(env);
+        let exception_clear = (**env).ExceptionClear.unwrap();
+        exception_clear(env);
+        throwable
+    };
+
+    let class = class_name(throwable);
+    let message_local = call_jvm_function!(self, c"java/lang/Throwable", c"getMessage", c"()Ljava/lang/String;", CallObjectMethod, throwable);
+    let message = Option::<String>::from_kotlin_object(message_local).unwrap_or_default();
+
+    delete_local_ref(env, throwable);
+    Err(KsonJniError { class, message })
+}
+
 pub(super) fn panic_upon_exception(env: *mut JNIEnv) {
     let has_exception = unsafe {
         let exception_check = (**env).ExceptionCheck.unwrap();
@@ -338,7 +666,36 @@ pub(super) fn enum_ordinal(class_name: &CStr, obj: jobject) -> i32 {
 }
 
 pub(super) fn to_kotlin_list<T: ToKotlinObject>(list: &[T]) -> KotlinPtr {
-    unimplemented!()
+    let (env, _detach_guard) = attach_thread_to_java_vm();
+    let class = get_class(env, c"java/util/ArrayList");
+    let constructor = get_method(env, class.as_kotlin_object(), c"<init>", c"()V");
+    let java_list = unsafe { (**env).NewObject.unwrap()(env, class.as_kotlin_object(), constructor) };
+    panic_upon_exception(env);
+
+    for element in list {
+        let element_ptr = element.to_kotlin_object();
+        call_jvm_function!(self, c"java/util/List", c"add", c"(Ljava/lang/Object;)Z", CallBooleanMethod, java_list, element_ptr.as_kotlin_object());
+    }
+
+    to_gc_global_ref(env, java_list)
+}
+
+pub(super) fn to_kotlin_map<K: ToKotlinObject, V: ToKotlinObject>(
+    entries: &[(K, V)],
+) -> KotlinPtr {
+    let (env, _detach_guard) = attach_thread_to_java_vm();
+    let class = get_class(env, c"java/util/HashMap");
+    let constructor = get_method(env, class.as_kotlin_object(), c"<init>", c"()V");
+    let java_map = unsafe { (**env).NewObject.unwrap()(env, class.as_kotlin_object(), constructor) };
+    panic_upon_exception(env);
+
+    for (key, value) in entries {
+        let key_ptr = key.to_kotlin_object();
+        let value_ptr = value.to_kotlin_object();
+        call_jvm_function!(self, c"java/util/Map", c"put", c"(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;", CallObjectMethod, java_map, key_ptr.as_kotlin_object(), value_ptr.as_kotlin_object());
+    }
+
+    to_gc_global_ref(env, java_map)
 }
 
 pub(super) fn from_kotlin_list<T: FromKotlinObject>(list: jobject) -> Vec<T> {
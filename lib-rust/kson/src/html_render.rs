@@ -0,0 +1,66 @@
+//! Tokenize-to-HTML rendering for documentation sites and web playgrounds.
+//!
+//! [`render_html`] runs the tokenizer over KSON source and emits HTML in which
+//! every token is wrapped in `<span class="kson-…">`, the class derived from
+//! [`TokenType::name`] lowercased, with the token text HTML-escaped. Whitespace
+//! and newlines pass through verbatim so the output drops straight into a
+//! `<pre>` block, and an `EmbedContent` span carries an extra
+//! `kson-embed-<tag>` class reflecting its [`TokenType::EmbedTag`] so embedded
+//! languages can be themed or further highlighted. This gives web surfaces
+//! syntax-highlighted KSON without reimplementing the lexer in JavaScript.
+
+use crate::{Kson, TokenType};
+
+/// Render `source` as a sequence of classed `<span>`s, suitable for dropping
+/// into a `<pre>` block.
+pub fn render_html(source: &str) -> String {
+    let analysis = Kson::analyze(source, None);
+
+    let mut out = String::new();
+    let mut current_tag: Option<String> = None;
+
+    for token in analysis.tokens() {
+        let token_type = token.token_type();
+        if matches!(token_type, TokenType::Eof) {
+            continue;
+        }
+        if matches!(token_type, TokenType::EmbedOpenDelim) {
+            current_tag = None;
+        }
+        if matches!(token_type, TokenType::EmbedTag) {
+            current_tag = Some(token.text());
+        }
+
+        let text = token.text();
+        let mut class = format!("kson-{}", token_type.name().to_lowercase());
+        if matches!(token_type, TokenType::EmbedContent) {
+            if let Some(tag) = &current_tag {
+                class.push_str(&format!(" kson-embed-{}", tag.to_lowercase()));
+            }
+        }
+
+        out.push_str(&format!(
+            "<span class=\"{}\">{}</span>",
+            class,
+            escape_html(&text)
+        ));
+    }
+
+    out
+}
+
+/// Escape the HTML-significant characters in `text`, leaving newlines intact.
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
@@ -0,0 +1,223 @@
+//! Incremental re-tokenization and semantic highlighting over [`Token`] streams.
+//!
+//! [`Analysis`] exposes a flat `Vec<Token>`, but editors that re-run
+//! [`Kson::analyze`] on every keystroke pay for a full JNI round-trip per edit.
+//! [`reanalyze`] takes the previous [`Analysis`], the old source, a described
+//! [`Edit`], and the new source, and rebuilds the token stream while reusing the
+//! tokens that the edit could not have touched: the prefix before the edit is
+//! kept verbatim, the suffix after it is shifted by the edit's line delta, and
+//! only a bounded window of whole lines around the edit is handed back to the
+//! Kotlin tokenizer. When an edit intersects a multi-line token (a multi-line
+//! embed, say), whose window cannot be tokenized in isolation, [`reanalyze`]
+//! falls back to a full [`Kson::analyze`] so its output always matches a full
+//! re-analysis. [`highlight`] maps each token's [`TokenType`] to a
+//! tree-sitter-style [`HighlightClass`], giving editors a ready-made syntax feed.
+
+use crate::{Analysis, Kson, Position, Token, TokenType};
+
+/// A source edit: the half-open `[start, end)` [`Position`] range (in the *old*
+/// source) that was replaced, and the text inserted in its place.
+#[derive(Clone)]
+pub struct Edit {
+    start: Position,
+    end: Position,
+    inserted: String,
+}
+
+impl Edit {
+    /// An edit replacing `start..end` with `inserted`.
+    pub fn new(start: Position, end: Position, inserted: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            inserted: inserted.into(),
+        }
+    }
+}
+
+/// Re-tokenize `new_source` after `edit` by reusing the unaffected tokens of
+/// `previous`, re-running the tokenizer only on the whole lines the edit spans.
+///
+/// `old_source` is accepted for symmetry and future span-precise windowing; the
+/// current whole-line strategy derives everything it needs from `edit` and
+/// `new_source`.
+pub fn reanalyze(
+    previous: &Analysis,
+    old_source: &str,
+    edit: &Edit,
+    new_source: &str,
+) -> Vec<Token> {
+    let _ = old_source;
+    let old_tokens = previous.tokens();
+
+    let edit_start_line = edit.start.line();
+    let edit_end_line = edit.end.line();
+
+    // A whole-line window is only context-free when no multi-line token (a
+    // multi-line embed or its content) straddles the edited region: cutting a
+    // window inside such a token and tokenizing it in isolation yields garbage.
+    // When the edit intersects one, fall back to a full re-analysis, which is
+    // always output-equivalent.
+    let intersects_multiline_token = old_tokens.iter().any(|token| {
+        let spans_multiple_lines = token.start().line() != token.end().line();
+        let before_edit = token.end().line() < edit_start_line;
+        let after_edit = token.start().line() > edit_end_line;
+        spans_multiple_lines && !before_edit && !after_edit
+    });
+    if intersects_multiline_token {
+        return Kson::analyze(new_source, None).tokens();
+    }
+
+    // Lines removed vs. inserted determines how far the suffix slides.
+    let removed_newlines = edit_end_line - edit_start_line;
+    let inserted_newlines = edit.inserted.matches('\n').count() as i32;
+    let line_delta = inserted_newlines - removed_newlines;
+
+    // The window covers the whole lines the edit now occupies in `new_source`;
+    // whole-line boundaries are context-free, so tokenizing them in isolation
+    // matches what a full re-analysis would produce.
+    let window_last_line = edit_start_line + inserted_newlines;
+    let window = slice_lines(new_source, edit_start_line, window_last_line);
+
+    let mut out = Vec::new();
+
+    // Prefix: tokens that end before the edited region — reused verbatim.
+    for token in &old_tokens {
+        if token.end().line() < edit_start_line {
+            out.push(token.clone());
+        }
+    }
+
+    // Window: freshly tokenized, rebased onto the window's first line. The
+    // substring's synthetic `Eof` is dropped; the document's own `Eof` rides
+    // along in the suffix (or is re-appended below when the suffix is empty).
+    let window_analysis = Kson::analyze(&window, None);
+    for token in window_analysis.tokens() {
+        if matches!(token.token_type(), TokenType::Eof) {
+            continue;
+        }
+        out.push(rebased(&token, edit_start_line));
+    }
+
+    // Suffix: tokens that start after the edited region — shifted by `line_delta`.
+    let mut saw_suffix = false;
+    for token in &old_tokens {
+        if token.start().line() > edit_end_line {
+            out.push(rebased(token, line_delta));
+            saw_suffix = true;
+        }
+    }
+
+    // Edits on the final line have no suffix to carry the trailing `Eof`; pull
+    // it from the rebased window analysis so the stream stays well-formed.
+    if !saw_suffix {
+        if let Some(eof) = window_analysis
+            .tokens()
+            .into_iter()
+            .find(|t| matches!(t.token_type(), TokenType::Eof))
+        {
+            out.push(rebased(&eof, edit_start_line));
+        }
+    }
+
+    out
+}
+
+/// Rebuild `token` with its `start`/`end` lines shifted by `line_delta`.
+fn rebased(token: &Token, line_delta: i32) -> Token {
+    let start = token.start();
+    let end = token.end();
+    Token::new(
+        token.token_type(),
+        &token.text(),
+        Position::new(start.line() + line_delta, start.column()),
+        Position::new(end.line() + line_delta, end.column()),
+    )
+}
+
+/// Extract the 0-based line range `[first, last]` of `source`, inclusive,
+/// rejoined with `\n`.
+fn slice_lines(source: &str, first: i32, last: i32) -> String {
+    let first = first.max(0) as usize;
+    let last = last.max(0) as usize;
+    source
+        .split('\n')
+        .skip(first)
+        .take(last.saturating_sub(first) + 1)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A tree-sitter-style semantic category for a [`Token`], suitable for driving
+/// an editor theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightClass {
+    /// Structural punctuation: braces, brackets, colon, comma, dot, dashes.
+    Punctuation,
+    /// Literal keywords `true`/`false`/`null`.
+    Keyword,
+    /// String bodies and their delimiters, and bare unquoted strings.
+    String,
+    /// Numeric literals.
+    Number,
+    /// Line comments.
+    Comment,
+    /// Embed delimiters, tags, and embedded content.
+    Embed,
+    /// Insignificant whitespace.
+    Whitespace,
+    /// Illegal characters the tokenizer could not classify.
+    Invalid,
+    /// End-of-file sentinel.
+    Eof,
+}
+
+/// Map a [`TokenType`] to its [`HighlightClass`].
+pub fn highlight_class(token_type: TokenType) -> HighlightClass {
+    match token_type {
+        TokenType::CurlyBraceL
+        | TokenType::CurlyBraceR
+        | TokenType::SquareBracketL
+        | TokenType::SquareBracketR
+        | TokenType::AngleBracketL
+        | TokenType::AngleBracketR
+        | TokenType::Colon
+        | TokenType::Dot
+        | TokenType::EndDash
+        | TokenType::Comma
+        | TokenType::ListDash => HighlightClass::Punctuation,
+        TokenType::True | TokenType::False | TokenType::Null => HighlightClass::Keyword,
+        TokenType::UnquotedString
+        | TokenType::StringOpenQuote
+        | TokenType::StringCloseQuote
+        | TokenType::StringContent => HighlightClass::String,
+        TokenType::Number => HighlightClass::Number,
+        TokenType::Comment => HighlightClass::Comment,
+        TokenType::EmbedOpenDelim
+        | TokenType::EmbedCloseDelim
+        | TokenType::EmbedTag
+        | TokenType::EmbedPreambleNewline
+        | TokenType::EmbedContent => HighlightClass::Embed,
+        TokenType::Whitespace => HighlightClass::Whitespace,
+        TokenType::IllegalChar => HighlightClass::Invalid,
+        TokenType::Eof => HighlightClass::Eof,
+    }
+}
+
+/// A token paired with the semantic class an editor should paint it.
+pub struct Highlight {
+    pub token: Token,
+    pub class: HighlightClass,
+}
+
+/// Produce a highlight feed for every token in `analysis`, in source order.
+pub fn highlight(analysis: &Analysis) -> Vec<Highlight> {
+    analysis
+        .tokens()
+        .into_iter()
+        .map(|token| {
+            let class = highlight_class(token.token_type());
+            Highlight { token, class }
+        })
+        .collect()
+}
@@ -0,0 +1,241 @@
+//! A jq-style path query engine over [`KsonValue`] trees.
+//!
+//! Modeled on the xq/jq value navigation system, this evaluates a selector
+//! expression against a root node and yields matching children. It supports
+//! field access (`.name`), array indexing (`[0]`, `[-1]`), slicing (`[1:3]`,
+//! `[:2]`, `[2:]`), wildcard iteration (`[]` or `.[]`), and recursive descent
+//! (`..`). Matches are the original JVM-backed [`KsonValue`] nodes, so their
+//! `start()`/`end()` [`Position`](crate::Position)s stay intact and results
+//! remain locatable in the source.
+//!
+//! The entry point is [`KsonValue::select`]; it is named `select` rather than
+//! `query` to sit alongside the simpler [`query`](crate::path_query) path API
+//! without colliding with it.
+
+use crate::KsonValue;
+
+/// A failure while parsing a selector expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// The expression was empty.
+    Empty,
+    /// An unexpected character was encountered at the given byte offset.
+    UnexpectedChar(usize, char),
+    /// A `[` was never closed by a matching `]`.
+    UnterminatedBracket,
+    /// A bracket held something that was not an index, slice, or `*`.
+    InvalidSelector(String),
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::Empty => f.write_str("empty selector"),
+            QueryError::UnexpectedChar(at, c) => {
+                write!(f, "unexpected character {c:?} at offset {at}")
+            }
+            QueryError::UnterminatedBracket => f.write_str("unterminated '['"),
+            QueryError::InvalidSelector(s) => write!(f, "invalid selector: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Object key lookup.
+    Field(String),
+    /// Array element by index, negative counting from the end.
+    Index(i64),
+    /// Array slice `[start:end)`, each bound optional and possibly negative.
+    Slice(Option<i64>, Option<i64>),
+    /// Every array element / every object value.
+    Iterate,
+    /// This node and all of its descendants.
+    Descend,
+}
+
+impl KsonValue {
+    /// Evaluate a jq-style selector against this node, returning every matching
+    /// descendant in document order. See the [module docs](crate::jq) for the
+    /// supported grammar.
+    pub fn select(&self, selector: &str) -> Result<Vec<KsonValue>, QueryError> {
+        let segments = parse(selector)?;
+        let mut current = vec![self.clone()];
+        for segment in &segments {
+            let mut next = Vec::new();
+            for node in &current {
+                apply(segment, node, &mut next);
+            }
+            current = next;
+        }
+        Ok(current)
+    }
+}
+
+fn parse(selector: &str) -> Result<Vec<Segment>, QueryError> {
+    let bytes = selector.as_bytes();
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'.' => {
+                if bytes.get(i + 1) == Some(&b'.') {
+                    segments.push(Segment::Descend);
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            b'[' => {
+                let close = selector[i..]
+                    .find(']')
+                    .map(|offset| i + offset)
+                    .ok_or(QueryError::UnterminatedBracket)?;
+                segments.push(parse_bracket(&selector[i + 1..close])?);
+                i = close + 1;
+            }
+            b if is_ident_start(b) => {
+                let start = i;
+                while i < bytes.len() && is_ident_char(bytes[i]) {
+                    i += 1;
+                }
+                segments.push(Segment::Field(selector[start..i].to_string()));
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            other => return Err(QueryError::UnexpectedChar(i, other as char)),
+        }
+    }
+    if segments.is_empty() {
+        return Err(QueryError::Empty);
+    }
+    Ok(segments)
+}
+
+fn parse_bracket(inner: &str) -> Result<Segment, QueryError> {
+    let inner = inner.trim();
+    if inner.is_empty() || inner == "*" {
+        return Ok(Segment::Iterate);
+    }
+    if let Some((lo, hi)) = inner.split_once(':') {
+        let lo = parse_bound(lo)?;
+        let hi = parse_bound(hi)?;
+        return Ok(Segment::Slice(lo, hi));
+    }
+    inner
+        .parse::<i64>()
+        .map(Segment::Index)
+        .map_err(|_| QueryError::InvalidSelector(inner.to_string()))
+}
+
+fn parse_bound(text: &str) -> Result<Option<i64>, QueryError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse::<i64>()
+        .map(Some)
+        .map_err(|_| QueryError::InvalidSelector(text.to_string()))
+}
+
+fn apply(segment: &Segment, node: &KsonValue, out: &mut Vec<KsonValue>) {
+    match segment {
+        Segment::Field(name) => {
+            if let KsonValue::KsonObject(object) = node {
+                if let Some(value) = object.properties().get(name) {
+                    out.push(value.clone());
+                }
+            }
+        }
+        Segment::Index(index) => {
+            if let KsonValue::KsonArray(array) = node {
+                let elements = array.elements();
+                if let Some(resolved) = resolve_index(*index, elements.len()) {
+                    out.push(elements[resolved].clone());
+                }
+            }
+        }
+        Segment::Slice(lo, hi) => {
+            if let KsonValue::KsonArray(array) = node {
+                let elements = array.elements();
+                let len = elements.len();
+                let start = clamp_bound(lo.unwrap_or(0), len);
+                let end = clamp_bound(hi.unwrap_or(len as i64), len);
+                if start < end {
+                    out.extend(elements[start..end].iter().cloned());
+                }
+            }
+        }
+        Segment::Iterate => match node {
+            KsonValue::KsonArray(array) => out.extend(array.elements()),
+            KsonValue::KsonObject(object) => out.extend(ordered_values(object)),
+            _ => {}
+        },
+        Segment::Descend => descend(node, out),
+    }
+}
+
+fn descend(node: &KsonValue, out: &mut Vec<KsonValue>) {
+    out.push(node.clone());
+    match node {
+        KsonValue::KsonArray(array) => {
+            for element in array.elements() {
+                descend(&element, out);
+            }
+        }
+        KsonValue::KsonObject(object) => {
+            for value in ordered_values(object) {
+                descend(&value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An object's values in document order — sorted by the source `start()`
+/// position of each key, since `properties()` iterates the backing `HashMap`
+/// arbitrarily.
+fn ordered_values(object: &crate::kson_value::KsonObject) -> Vec<KsonValue> {
+    let positions: std::collections::HashMap<String, (i32, i32)> = object
+        .property_keys()
+        .into_iter()
+        .map(|(name, key)| {
+            let start = key.start();
+            (name, (start.line(), start.column()))
+        })
+        .collect();
+    let mut keyed: Vec<(String, KsonValue)> = object.properties().into_iter().collect();
+    keyed.sort_by_key(|(key, _)| {
+        positions
+            .get(key)
+            .copied()
+            .unwrap_or((i32::MAX, i32::MAX))
+    });
+    keyed.into_iter().map(|(_, value)| value).collect()
+}
+
+/// Resolve a possibly-negative index into a concrete position, or `None` if it
+/// falls outside `0..len`.
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { index + len as i64 } else { index };
+    if resolved < 0 || resolved as usize >= len {
+        None
+    } else {
+        Some(resolved as usize)
+    }
+}
+
+/// Clamp a possibly-negative slice bound into `0..=len`.
+fn clamp_bound(bound: i64, len: usize) -> usize {
+    let resolved = if bound < 0 { bound + len as i64 } else { bound };
+    resolved.clamp(0, len as i64) as usize
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+}
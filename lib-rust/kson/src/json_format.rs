@@ -0,0 +1,117 @@
+//! Formatting controls for KSON-to-JSON transpilation.
+//!
+//! The JVM-backed [`transpile_options::Json`](crate::transpile_options::Json)
+//! only carries `retain_embed_tags`, so `Kson::to_json` output has no layout
+//! control. [`JsonFormat`] wraps that call with the configurable encoding knobs
+//! familiar from classic JSON libraries — indent unit (spaces of a given width,
+//! or tabs), a compact single-line mode, and trailing-newline control — so the
+//! same KSON source can be rendered as canonical/diffable or as minified JSON
+//! without the caller post-processing the result.
+//!
+//! Object keys are always emitted in lexicographic order: the reformatting step
+//! routes through [`serde_json::Value`], whose object model is ordered by key,
+//! so key order is a property of the round trip rather than a configurable knob.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{result, transpile_options, Kson};
+
+/// The indentation unit used in pretty (non-compact) output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndentUnit {
+    /// `width` spaces per level.
+    Spaces(usize),
+    /// A single tab per level.
+    Tabs,
+}
+
+/// A builder bundling the JSON transpilation options.
+#[derive(Clone, Debug)]
+pub struct JsonFormat {
+    retain_embed_tags: bool,
+    indent: IndentUnit,
+    compact: bool,
+    trailing_newline: bool,
+}
+
+impl Default for JsonFormat {
+    fn default() -> Self {
+        Self {
+            retain_embed_tags: false,
+            indent: IndentUnit::Spaces(2),
+            compact: false,
+            trailing_newline: false,
+        }
+    }
+}
+
+impl JsonFormat {
+    /// A formatter with the default layout: two-space indent, pretty, no
+    /// trailing newline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep embed tags in the transpiled output (see
+    /// [`transpile_options::Json`](crate::transpile_options::Json)).
+    pub fn retain_embed_tags(mut self, retain: bool) -> Self {
+        self.retain_embed_tags = retain;
+        self
+    }
+
+    /// Set the indentation unit for pretty output.
+    pub fn indent(mut self, indent: IndentUnit) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Emit everything on a single line with no insignificant whitespace.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Append a trailing newline to the output.
+    pub fn trailing_newline(mut self, trailing: bool) -> Self {
+        self.trailing_newline = trailing;
+        self
+    }
+
+    /// Transpile `kson` to JSON, applying this format. Parse or transpile errors
+    /// surface as a [`result::Failure`]; if the transpiler's own output is not
+    /// re-parseable as JSON it is returned unchanged.
+    pub fn transpile(&self, kson: &str) -> Result<String, result::Failure> {
+        let success = Kson::to_json(kson, transpile_options::Json::new(self.retain_embed_tags))?;
+        let output = success.output();
+        let value: Value = match serde_json::from_str(&output) {
+            Ok(value) => value,
+            Err(_) => return Ok(self.finish(output)),
+        };
+        Ok(self.finish(self.render(&value)))
+    }
+
+    fn render(&self, value: &Value) -> String {
+        if self.compact {
+            return serde_json::to_string(value).unwrap_or_default();
+        }
+        let unit = match &self.indent {
+            IndentUnit::Spaces(width) => " ".repeat(*width),
+            IndentUnit::Tabs => "\t".to_string(),
+        };
+        let mut buffer = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+        let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+        if value.serialize(&mut serializer).is_err() {
+            return serde_json::to_string_pretty(value).unwrap_or_default();
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    fn finish(&self, mut rendered: String) -> String {
+        if self.trailing_newline && !rendered.ends_with('\n') {
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
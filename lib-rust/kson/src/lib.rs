@@ -1,8 +1,42 @@
+#![feature(auto_traits, negative_impls)]
+
 mod generated;
+pub mod annotated;
+pub mod binary;
+pub mod canonical;
+pub mod diagnostic;
+pub mod embed_decoders;
+pub mod embed_formatters;
+pub mod embed_handlers;
+pub mod html_render;
+pub mod incremental;
+pub mod jq;
+pub mod json_format;
+pub mod lsp_tokens;
+pub mod number_fidelity;
+pub mod ordered_properties;
+pub mod owned;
+pub mod path_query;
+pub mod precise_number;
+pub mod query_expr;
+pub mod reanalysis;
+pub mod render;
+pub mod schema_diagnostics;
+pub mod semantic_tokens;
+pub mod serde_family;
+pub mod serde_json_bridge;
+pub mod serde_json_interop;
+pub mod serde_support;
+pub mod token_stream;
+pub mod token_type_lookup;
+pub mod tree_format;
+pub mod triage;
+pub mod utf16_index;
 #[cfg(test)]
 mod test;
 
 pub use generated::*;
+pub use serde_support::{from_kson_value, from_str, to_kson_value, to_string};
 
 fn kson_result_into_rust_result(r: Result) -> std::result::Result<result::Success, result::Failure> {
     match r {
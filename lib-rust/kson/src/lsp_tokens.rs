@@ -0,0 +1,122 @@
+//! LSP semantic-tokens encoding keyed on [`TokenType`].
+//!
+//! This encoder turns a KSON token sequence into the Language Server Protocol
+//! "semantic tokens" integer array. Unlike [`Analysis::semantic_tokens`](crate::Analysis::semantic_tokens),
+//! which operates on a whole [`Analysis`](crate::Analysis), [`encode`] works over
+//! any `&[Token]` slice and uses the fuller legend this chunk calls for —
+//! `UnquotedString` maps to `property`, `ListDash` to `operator`, and so on.
+//! Each emitted token becomes a 5-tuple `(deltaLine, deltaStartChar, length,
+//! tokenType, tokenModifiers)`; lengths are counted in UTF-16 code units, tokens
+//! are emitted in document order, trivia is skipped, and a multi-line token is
+//! reported on its start line.
+
+use crate::{Token, TokenType};
+
+/// The ordered legend a [`encode`]d array's indices refer to.
+pub struct SemanticTokensLegend {
+    /// Token-type names, indexed by the `tokenType` field of each tuple.
+    pub token_types: Vec<String>,
+    /// Token-modifier names, indexed bitwise by the `tokenModifiers` field.
+    pub token_modifiers: Vec<String>,
+}
+
+// Indices into `SemanticTokensLegend::token_types`; kept in sync with [`legend`]
+// and [`token_type_index`].
+const COMMENT: u32 = 0;
+const STRING: u32 = 1;
+const NUMBER: u32 = 2;
+const KEYWORD: u32 = 3;
+const OPERATOR: u32 = 4;
+const TYPE: u32 = 5;
+const PROPERTY: u32 = 6;
+
+/// The fixed legend mapping our [`TokenType`] variants onto LSP scopes.
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            "comment".to_string(),
+            "string".to_string(),
+            "number".to_string(),
+            "keyword".to_string(),
+            "operator".to_string(),
+            "type".to_string(),
+            "property".to_string(),
+        ],
+        token_modifiers: Vec::new(),
+    }
+}
+
+/// The legend index for `token_type`, or `None` for skipped trivia
+/// (whitespace, illegal chars, EOF).
+fn token_type_index(token_type: TokenType) -> Option<u32> {
+    let index = match token_type {
+        TokenType::Comment => COMMENT,
+        TokenType::StringOpenQuote
+        | TokenType::StringContent
+        | TokenType::StringCloseQuote
+        | TokenType::EmbedContent => STRING,
+        TokenType::Number => NUMBER,
+        TokenType::True | TokenType::False | TokenType::Null => KEYWORD,
+        TokenType::CurlyBraceL
+        | TokenType::CurlyBraceR
+        | TokenType::SquareBracketL
+        | TokenType::SquareBracketR
+        | TokenType::AngleBracketL
+        | TokenType::AngleBracketR
+        | TokenType::Colon
+        | TokenType::Dot
+        | TokenType::EndDash
+        | TokenType::Comma
+        | TokenType::ListDash
+        | TokenType::EmbedOpenDelim
+        | TokenType::EmbedCloseDelim
+        | TokenType::EmbedPreambleNewline => OPERATOR,
+        TokenType::EmbedTag => TYPE,
+        TokenType::UnquotedString => PROPERTY,
+        TokenType::Whitespace | TokenType::IllegalChar | TokenType::Eof => return None,
+    };
+    Some(index)
+}
+
+/// Encode `tokens` as the LSP semantic-tokens `data` array. Tokens are taken in
+/// the order given; lengths are in UTF-16 code units.
+pub fn encode(tokens: &[Token]) -> Vec<u32> {
+    let mut data = Vec::new();
+    let mut prev_line = 0i32;
+    let mut prev_char = 0i32;
+
+    for token in tokens {
+        let Some(type_index) = token_type_index(token.token_type()) else {
+            continue;
+        };
+
+        let start = token.start();
+        let line = start.line();
+        let char_start = start.column();
+        let length = utf16_length(&token.text());
+
+        let delta_line = (line - prev_line).max(0) as u32;
+        let delta_char = if delta_line == 0 {
+            (char_start - prev_char).max(0) as u32
+        } else {
+            char_start.max(0) as u32
+        };
+
+        data.extend_from_slice(&[delta_line, delta_char, length, type_index, 0]);
+        prev_line = line;
+        prev_char = char_start;
+    }
+
+    data
+}
+
+/// UTF-16 code-unit length of a token's first line (multi-line tokens are
+/// reported on their start line).
+fn utf16_length(text: &str) -> u32 {
+    text.split('\n')
+        .next()
+        .unwrap_or(text)
+        .chars()
+        .map(|ch| ch.len_utf16() as u32)
+        .sum()
+}
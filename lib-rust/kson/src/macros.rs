@@ -11,7 +11,7 @@ macro_rules! declare_kotlin_object {
         impl FromKotlinObject for $type_name {
             fn from_kotlin_object(obj: kson_sys::kson_KNativePtr) -> Self {
                 let kson_ref = KsonPtr {
-                    inner: std::sync::Arc::new(OwnedKotlinPtr { inner: obj }),
+                    inner: std::sync::Arc::new(OwnedKotlinPtr::new(obj)),
                 };
 
                 Self { kson_ref }
@@ -26,6 +26,18 @@ macro_rules! declare_kotlin_object {
     };
 }
 
+macro_rules! impl_kotlin_typed {
+    ($type_name:ty, $kotlin_ty:expr) => {
+        impl util::KotlinTyped for $type_name {
+            fn kotlin_type() -> util::KotlinType {
+                util::KotlinType {
+                    inner: unsafe { $kotlin_ty._type.unwrap()() },
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_kotlin_object_for_enum {
     (
         $enum_type:ty,
@@ -0,0 +1,155 @@
+//! Full-fidelity accessors for KSON numbers.
+//!
+//! [`Integer::value`](crate::kson_value::kson_number::Integer::value) returns an
+//! `i32` and [`Decimal`](crate::kson_value::kson_number::Decimal) exposes an
+//! `f64`, both of which lose information the original literal carried — a large
+//! integer overflows, and `007`/`3E5` are reformatted. These accessors recover
+//! the lost fidelity by slicing the number's source text directly, using the
+//! node's `start()`/`end()` span, rather than reformatting the parsed value:
+//! [`raw_lexeme`] returns the exact source spelling, and [`value_big`] parses
+//! that spelling into an arbitrary-precision
+//! [`PreciseNumber`](crate::precise_number::PreciseNumber) (the crate's own
+//! lossless numeric type, used here rather than pulling in a big-integer
+//! dependency). [`value_i64`] remains a convenience widening of the parsed
+//! `i32`.
+//!
+//! [`value_i64`]: crate::kson_value::kson_number::Integer::value_i64
+//! [`value_big`]: crate::kson_value::kson_number::Integer::value_big
+//! [`raw_lexeme`]: crate::kson_value::kson_number::Integer::raw_lexeme
+//!
+//! The source text the literal was parsed from must be supplied, since the JVM
+//! binding does not retain it. When a span cannot be resolved within `source`
+//! the accessors fall back to the parsed value's reformatted text.
+
+use crate::kson_value::kson_number::{Decimal, Integer};
+use crate::precise_number::PreciseNumber;
+use crate::Position;
+
+impl Integer {
+    /// The parsed value widened to `i64`.
+    ///
+    /// Note this widens the already-parsed [`value`](Integer::value); it does
+    /// not recover integers the `i32` binding truncated on the way in — use
+    /// [`value_big`](Integer::value_big) for that.
+    pub fn value_i64(&self) -> i64 {
+        i64::from(self.value())
+    }
+
+    /// The value as an arbitrary-precision [`PreciseNumber`], parsed from the
+    /// original source spelling so integers outside the `i32` range survive.
+    pub fn value_big(&self, source: &str) -> PreciseNumber {
+        PreciseNumber::parse(&self.raw_lexeme(source))
+            .unwrap_or_else(|| PreciseNumber::parse(&self.value().to_string()).expect("i32 literal"))
+    }
+
+    /// The exact source spelling of this number, sliced from `source` by the
+    /// node's span, preserving leading zeros and exponent notation. Falls back
+    /// to the reformatted [`value`](Integer::value) if the span does not
+    /// resolve.
+    pub fn raw_lexeme(&self, source: &str) -> String {
+        slice_span(source, &self.start(), &self.end())
+            .unwrap_or_else(|| self.value().to_string())
+    }
+}
+
+impl Decimal {
+    /// The exact source spelling of this number, sliced from `source` by the
+    /// node's span, preserving exponent notation and trailing zeros. Falls back
+    /// to a normalized rendering of [`value`](Decimal::value) if the span does
+    /// not resolve.
+    pub fn raw_lexeme(&self, source: &str) -> String {
+        slice_span(source, &self.start(), &self.end())
+            .unwrap_or_else(|| format_decimal(self.value()))
+    }
+}
+
+/// Slice the substring of `source` spanned by `start..end`, or `None` if either
+/// position does not resolve within `source`.
+fn slice_span(source: &str, start: &Position, end: &Position) -> Option<String> {
+    let from = byte_offset(source, start.line(), start.column())?;
+    let to = byte_offset(source, end.line(), end.column())?;
+    source.get(from..to).map(str::to_string)
+}
+
+/// Resolve a 0-based `(line, column)` position to a byte offset into `source`,
+/// counting columns in characters. Returns `None` for negative coordinates or a
+/// position past the end of its line.
+fn byte_offset(source: &str, line: i32, column: i32) -> Option<usize> {
+    if line < 0 || column < 0 {
+        return None;
+    }
+    let mut offset = 0;
+    let mut current_line = 0;
+    for piece in source.split_inclusive('\n') {
+        if current_line == line {
+            let mut col = 0;
+            for ch in piece.chars() {
+                if col == column {
+                    return Some(offset);
+                }
+                if ch == '\n' {
+                    break;
+                }
+                offset += ch.len_utf8();
+                col += 1;
+            }
+            return (col == column).then_some(offset);
+        }
+        offset += piece.len();
+        current_line += 1;
+    }
+    (current_line == line && column == 0).then_some(offset)
+}
+
+/// Render `value` as a decimal literal, keeping a trailing `.0` on integral
+/// values so they read back as decimals rather than integers.
+fn format_decimal(value: f64) -> String {
+    if value == value.trunc() && value.is_finite() {
+        format!("{value:.1}")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_offset, format_decimal, slice_span};
+    use crate::Position;
+
+    #[test]
+    fn byte_offset_counts_lines_and_columns() {
+        let source = "ab\ncde\nf";
+        assert_eq!(byte_offset(source, 0, 0), Some(0));
+        assert_eq!(byte_offset(source, 0, 2), Some(2)); // end of first line
+        assert_eq!(byte_offset(source, 1, 0), Some(3));
+        assert_eq!(byte_offset(source, 2, 1), Some(8)); // end of last line
+        assert_eq!(byte_offset(source, -1, 0), None);
+        assert_eq!(byte_offset(source, 0, 5), None); // past end of line
+    }
+
+    #[test]
+    fn slice_span_preserves_source_spelling() {
+        // A span over the raw literal recovers leading zeros and exponents.
+        let source = "x: 007\ny: 3E5";
+        assert_eq!(
+            slice_span(source, &Position::new(0, 3), &Position::new(0, 6)),
+            Some("007".to_string())
+        );
+        assert_eq!(
+            slice_span(source, &Position::new(1, 3), &Position::new(1, 6)),
+            Some("3E5".to_string())
+        );
+    }
+
+    #[test]
+    fn integral_decimals_keep_a_fractional_part() {
+        assert_eq!(format_decimal(1.0), "1.0");
+        assert_eq!(format_decimal(-42.0), "-42.0");
+    }
+
+    #[test]
+    fn fractional_decimals_render_as_is() {
+        assert_eq!(format_decimal(1.5), "1.5");
+        assert_eq!(format_decimal(0.25), "0.25");
+    }
+}
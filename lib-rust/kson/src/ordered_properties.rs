@@ -0,0 +1,56 @@
+//! Order-preserving property accessors for [`KsonObject`](kson_value::KsonObject).
+//!
+//! [`properties`](kson_value::KsonObject::properties) and
+//! [`property_keys`](kson_value::KsonObject::property_keys) return a
+//! [`HashMap`](std::collections::HashMap), whose iteration order is arbitrary —
+//! so `Debug`/serialization output is non-deterministic and a tool re-emitting
+//! KSON cannot reproduce the original key layout. These accessors return a
+//! `Vec<(String, KsonValue)>` in the object's own document order instead.
+//!
+//! Document order is recovered from [`property_keys`](kson_value::KsonObject::property_keys),
+//! whose [`KsonString`](kson_value::KsonString) keys each carry a source
+//! `start()` position: keys are ordered by that position, so `{b: 1, a: 2}`
+//! round-trips as `{b: 1, a: 2}` rather than being re-sorted. Any key whose
+//! position is unavailable sorts after the located ones.
+
+use std::collections::HashMap;
+
+use crate::{kson_value, KsonValue};
+
+impl kson_value::KsonObject {
+    /// The object's properties as key/value pairs in document order.
+    pub fn properties_ordered(&self) -> Vec<(String, KsonValue)> {
+        let positions = self.key_positions();
+        let mut pairs: Vec<(String, KsonValue)> = self.properties().into_iter().collect();
+        pairs.sort_by_key(|(key, _)| position_of(&positions, key));
+        pairs
+    }
+
+    /// The object's keys in the same document order as
+    /// [`properties_ordered`](Self::properties_ordered).
+    pub fn keys_ordered(&self) -> Vec<String> {
+        let positions = self.key_positions();
+        let mut keys: Vec<String> = self.properties().into_keys().collect();
+        keys.sort_by_key(|key| position_of(&positions, key));
+        keys
+    }
+
+    /// Map each key to its `(line, column)` source position, taken from the
+    /// [`KsonString`](kson_value::KsonString) key handles in
+    /// [`property_keys`](Self::property_keys).
+    fn key_positions(&self) -> HashMap<String, (i32, i32)> {
+        self.property_keys()
+            .into_iter()
+            .map(|(name, key)| {
+                let start = key.start();
+                (name, (start.line(), start.column()))
+            })
+            .collect()
+    }
+}
+
+/// The document position of `key`, or a sentinel sorting after all located
+/// keys when the position is unavailable.
+fn position_of(positions: &HashMap<String, (i32, i32)>, key: &str) -> (i32, i32) {
+    positions.get(key).copied().unwrap_or((i32::MAX, i32::MAX))
+}
@@ -0,0 +1,155 @@
+//! A native, owned mirror of the [`KsonValue`] tree.
+//!
+//! Every accessor on the JVM-backed [`KsonValue`] wrappers crosses the JNI
+//! boundary — `attach_thread_to_java_vm()` plus a `call_jvm_function!`
+//! round-trip — so walking a document of `N` nodes costs `O(N × fields)`
+//! crossings. [`KsonValue::to_owned`] drains the whole Kotlin tree into the
+//! [`OwnedKsonValue`] enum below in a single recursive traversal, capturing each
+//! node's value, `start`/`end` [`Position`] as plain Rust fields. Callers that
+//! only read — the common case — then operate entirely in Rust with no further
+//! VM attach.
+
+use crate::{kson_value, KsonValue, Position};
+
+/// A source [`Position`] read into plain `line`/`column` fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OwnedPosition {
+    pub line: i32,
+    pub column: i32,
+}
+
+impl OwnedPosition {
+    fn from(position: Position) -> Self {
+        Self {
+            line: position.line(),
+            column: position.column(),
+        }
+    }
+}
+
+/// The integer-vs-decimal distinction of a [`KsonNumber`](kson_value::KsonNumber),
+/// materialized into an owned scalar. The integer arm is kept at `i64` width to
+/// avoid re-narrowing, though it inherits the `i32` range limit of the
+/// underlying binding (see
+/// [`Integer::value_i64`](kson_value::kson_number::Integer::value_i64)).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OwnedNumber {
+    Integer(i64),
+    Decimal(f64),
+}
+
+/// An owned, JNI-free mirror of a [`KsonValue`] node, including its source span.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OwnedKsonValue {
+    Array {
+        elements: Vec<OwnedKsonValue>,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    Object {
+        properties: Vec<(String, OwnedKsonValue)>,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    String {
+        value: String,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    Number {
+        value: OwnedNumber,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    Boolean {
+        value: bool,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    Embed {
+        tag: Option<String>,
+        content: String,
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+    Null {
+        start: OwnedPosition,
+        end: OwnedPosition,
+    },
+}
+
+impl KsonValue {
+    /// Recursively drain this Kotlin tree into owned Rust data in a single
+    /// traversal. After this returns, no further VM attach is required to read
+    /// the tree.
+    pub fn to_owned(&self) -> OwnedKsonValue {
+        match self {
+            KsonValue::KsonArray(a) => OwnedKsonValue::Array {
+                elements: a.elements().iter().map(KsonValue::to_owned).collect(),
+                start: OwnedPosition::from(a.start()),
+                end: OwnedPosition::from(a.end()),
+            },
+            KsonValue::KsonObject(o) => {
+                // Preserve the document's own key order: the `properties`
+                // HashMap iterates arbitrarily, so re-key it by the source
+                // `start()` position each key carries in `property_keys`.
+                let positions: std::collections::HashMap<String, (i32, i32)> = o
+                    .property_keys()
+                    .into_iter()
+                    .map(|(name, key)| {
+                        let start = key.start();
+                        (name, (start.line(), start.column()))
+                    })
+                    .collect();
+                let mut properties: Vec<(String, OwnedKsonValue)> = o
+                    .properties()
+                    .into_iter()
+                    .map(|(key, value)| (key, value.to_owned()))
+                    .collect();
+                properties.sort_by_key(|(key, _)| {
+                    positions
+                        .get(key)
+                        .copied()
+                        .unwrap_or((i32::MAX, i32::MAX))
+                });
+                OwnedKsonValue::Object {
+                    properties,
+                    start: OwnedPosition::from(o.start()),
+                    end: OwnedPosition::from(o.end()),
+                }
+            }
+            KsonValue::KsonString(s) => OwnedKsonValue::String {
+                value: s.value(),
+                start: OwnedPosition::from(s.start()),
+                end: OwnedPosition::from(s.end()),
+            },
+            KsonValue::KsonNumber(n) => match n {
+                kson_value::KsonNumber::Integer(i) => OwnedKsonValue::Number {
+                    value: OwnedNumber::Integer(i.value_i64()),
+                    start: OwnedPosition::from(i.start()),
+                    end: OwnedPosition::from(i.end()),
+                },
+                kson_value::KsonNumber::Decimal(d) => OwnedKsonValue::Number {
+                    value: OwnedNumber::Decimal(d.value()),
+                    start: OwnedPosition::from(d.start()),
+                    end: OwnedPosition::from(d.end()),
+                },
+            },
+            KsonValue::KsonBoolean(b) => OwnedKsonValue::Boolean {
+                value: b.value(),
+                start: OwnedPosition::from(b.start()),
+                end: OwnedPosition::from(b.end()),
+            },
+            KsonValue::KsonEmbed(e) => OwnedKsonValue::Embed {
+                tag: e.tag(),
+                content: e.content(),
+                start: OwnedPosition::from(e.start()),
+                end: OwnedPosition::from(e.end()),
+            },
+            KsonValue::KsonNull(nil) => OwnedKsonValue::Null {
+                start: OwnedPosition::from(nil.start()),
+                end: OwnedPosition::from(nil.end()),
+            },
+        }
+    }
+}
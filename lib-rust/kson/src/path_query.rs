@@ -0,0 +1,215 @@
+//! A compact path-query subsystem over [`KsonValue`] trees.
+//!
+//! Rather than hand-writing recursive `match` over every `kson_value::*`
+//! variant, callers can compile a selector string into a [`KsonPath`] and run
+//! [`KsonValue::query`] to collect every matching node. Because each
+//! [`KsonValue`] carries its [`Position`](crate::Position), matches map straight
+//! back to source locations for tooling.
+//!
+//! # Grammar
+//!
+//! A path is a sequence of segments:
+//!
+//! - `name` — the value of that key when the node is a `KsonObject`.
+//! - `[n]` — element `n` of a `KsonArray`.
+//! - `[*]` or a trailing `.*` — every array element / every object value.
+//! - `..name` — recursive descent, matching `name` at any depth.
+//!
+//! A missing key, out-of-range index, or wildcard/descent over a scalar simply
+//! contributes nothing to the result.
+
+use crate::KsonValue;
+
+/// A parsed path selector, runnable against a [`KsonValue`] via
+/// [`KsonValue::query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Object key lookup.
+    Key(String),
+    /// Array index.
+    Index(usize),
+    /// All array elements / all object values.
+    Wildcard,
+    /// Recursive descent matching a key at any depth.
+    Descendant(String),
+}
+
+/// An error produced while parsing a [`KsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// A `[...]` index segment was left unterminated.
+    UnterminatedIndex,
+    /// The contents of a `[...]` segment were neither `*` nor an integer.
+    InvalidIndex(String),
+    /// A segment expected a key name but found none.
+    EmptyName,
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::UnterminatedIndex => f.write_str("unterminated `[` in path"),
+            PathError::InvalidIndex(s) => write!(f, "invalid index segment: `{s}`"),
+            PathError::EmptyName => f.write_str("empty key name in path"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl KsonPath {
+    /// Parse a selector string into a [`KsonPath`].
+    pub fn parse(input: &str) -> Result<Self, PathError> {
+        let mut segments = Vec::new();
+        let mut chars = input.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            match c {
+                '.' => {
+                    chars.next();
+                    if chars.peek() == Some(&'.') {
+                        chars.next();
+                        segments.push(Segment::Descendant(take_name(&mut chars)?));
+                    } else if chars.peek() == Some(&'*') {
+                        chars.next();
+                        segments.push(Segment::Wildcard);
+                    } else {
+                        segments.push(Segment::Key(take_name(&mut chars)?));
+                    }
+                }
+                '[' => {
+                    chars.next();
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => inner.push(c),
+                            None => return Err(PathError::UnterminatedIndex),
+                        }
+                    }
+                    if inner == "*" {
+                        segments.push(Segment::Wildcard);
+                    } else {
+                        let index = inner
+                            .parse::<usize>()
+                            .map_err(|_| PathError::InvalidIndex(inner))?;
+                        segments.push(Segment::Index(index));
+                    }
+                }
+                _ => segments.push(Segment::Key(take_name(&mut chars)?)),
+            }
+        }
+        Ok(KsonPath { segments })
+    }
+}
+
+fn take_name(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<String, PathError> {
+    let mut name = String::new();
+    while let Some(&c) = chars.peek() {
+        if c == '.' || c == '[' {
+            break;
+        }
+        name.push(c);
+        chars.next();
+    }
+    if name.is_empty() {
+        Err(PathError::EmptyName)
+    } else {
+        Ok(name)
+    }
+}
+
+impl KsonValue {
+    /// Evaluate `path` against this tree, returning every matching node.
+    pub fn query(&self, path: &KsonPath) -> Vec<KsonValue> {
+        let mut current = vec![self.clone()];
+        for segment in &path.segments {
+            let mut next = Vec::new();
+            for node in &current {
+                apply(segment, node, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Parse `path` and evaluate it, returning every matching node. A malformed
+    /// path yields an empty result.
+    pub fn query_str(&self, path: &str) -> Vec<KsonValue> {
+        match KsonPath::parse(path) {
+            Ok(path) => self.query(&path),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+fn apply(segment: &Segment, node: &KsonValue, out: &mut Vec<KsonValue>) {
+    match segment {
+        Segment::Key(key) => {
+            if let KsonValue::KsonObject(object) = node {
+                if let Some(value) = object.properties().get(key) {
+                    out.push(value.clone());
+                }
+            }
+        }
+        Segment::Index(index) => {
+            if let KsonValue::KsonArray(array) = node {
+                if let Some(element) = array.elements().get(*index) {
+                    out.push(element.clone());
+                }
+            }
+        }
+        Segment::Wildcard => match node {
+            KsonValue::KsonArray(array) => out.extend(array.elements()),
+            KsonValue::KsonObject(object) => out.extend(ordered_values(object)),
+            _ => {}
+        },
+        Segment::Descendant(key) => collect_descendants(key, node, out),
+    }
+}
+
+/// Depth-first collect every value bound to `key`, at any depth within `node`.
+fn collect_descendants(key: &str, node: &KsonValue, out: &mut Vec<KsonValue>) {
+    match node {
+        KsonValue::KsonObject(object) => {
+            if let Some(value) = object.properties().get(key) {
+                out.push(value.clone());
+            }
+            for value in ordered_values(object) {
+                collect_descendants(key, &value, out);
+            }
+        }
+        KsonValue::KsonArray(array) => {
+            for element in array.elements() {
+                collect_descendants(key, &element, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An object's values in document order — sorted by the source `start()`
+/// position of each key, since `properties()` iterates the backing `HashMap`
+/// arbitrarily.
+fn ordered_values(object: &crate::kson_value::KsonObject) -> Vec<KsonValue> {
+    let positions: std::collections::HashMap<String, (i32, i32)> = object
+        .property_keys()
+        .into_iter()
+        .map(|(name, key)| {
+            let start = key.start();
+            (name, (start.line(), start.column()))
+        })
+        .collect();
+    let mut keyed: Vec<(String, KsonValue)> = object.properties().into_iter().collect();
+    keyed.sort_by_key(|(key, _)| {
+        positions
+            .get(key)
+            .copied()
+            .unwrap_or((i32::MAX, i32::MAX))
+    });
+    keyed.into_iter().map(|(_, value)| value).collect()
+}
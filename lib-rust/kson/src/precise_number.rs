@@ -0,0 +1,212 @@
+//! Arbitrary-precision numeric values for KSON.
+//!
+//! [`kson_number::Integer::new`](crate::kson_value::kson_number::Integer::new)
+//! takes an `i32` and [`kson_number::Decimal::new`](crate::kson_value::kson_number::Decimal::new)
+//! takes an `f64`, so large integers overflow and long decimals lose precision —
+//! the same hazard jq-style tools avoid by keeping exact numeric
+//! representations. [`PreciseNumber`] holds a numeric literal losslessly as a
+//! normalized coefficient-and-exponent pair, so `1e9000`, a 40-digit integer,
+//! and `0.1` all survive intact.
+//!
+//! Because the value is normalized on construction, `Eq`/`Hash` compare *by
+//! numeric value* rather than by spelling: `"1.0"`, `"1"`, and `"10e-1"` are all
+//! equal and hash identically. [`as_i64`](PreciseNumber::as_i64) and
+//! [`as_f64`](PreciseNumber::as_f64) give convenient (possibly lossy) views,
+//! while [`as_str`](PreciseNumber::as_str) is the lossless canonical form.
+
+use crate::{kson_value, KsonValue};
+
+/// A numeric value kept at arbitrary precision.
+///
+/// The value equals `(-1)^negative × coefficient × 10^exponent`, with
+/// `coefficient` a decimal digit string carrying no leading or trailing zeros.
+/// Zero is canonicalized to coefficient `"0"`, exponent `0`, non-negative.
+#[derive(Clone, Debug)]
+pub struct PreciseNumber {
+    negative: bool,
+    coefficient: String,
+    exponent: i64,
+}
+
+impl PreciseNumber {
+    /// Parse a decimal numeric literal (optional sign, digits, fraction, and
+    /// `e`/`E` exponent) into a lossless value. Returns `None` if `literal` is
+    /// not a well-formed number.
+    pub fn parse(literal: &str) -> Option<Self> {
+        let literal = literal.trim();
+        let (negative, rest) = match literal.as_bytes().first() {
+            Some(b'-') => (true, &literal[1..]),
+            Some(b'+') => (false, &literal[1..]),
+            _ => (false, literal),
+        };
+
+        let (mantissa, exp_part) = match rest.split_once(['e', 'E']) {
+            Some((m, e)) => (m, Some(e)),
+            None => (rest, None),
+        };
+
+        let (int_part, frac_part) = match mantissa.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (mantissa, ""),
+        };
+
+        if int_part.is_empty() && frac_part.is_empty() {
+            return None;
+        }
+        if !int_part.bytes().all(|b| b.is_ascii_digit())
+            || !frac_part.bytes().all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let explicit_exp = match exp_part {
+            Some(e) => e.parse::<i64>().ok()?,
+            None => 0,
+        };
+
+        let mut digits = String::with_capacity(int_part.len() + frac_part.len());
+        digits.push_str(int_part);
+        digits.push_str(frac_part);
+        let exponent = explicit_exp - frac_part.len() as i64;
+
+        Some(Self::normalized(negative, digits, exponent))
+    }
+
+    fn normalized(negative: bool, digits: String, mut exponent: i64) -> Self {
+        let trimmed = digits.trim_start_matches('0');
+        let mut coefficient: String = trimmed.to_string();
+        // Fold trailing zeros into the exponent so equal values share a form.
+        while coefficient.ends_with('0') {
+            coefficient.pop();
+            exponent += 1;
+        }
+        if coefficient.is_empty() {
+            return Self {
+                negative: false,
+                coefficient: "0".to_string(),
+                exponent: 0,
+            };
+        }
+        Self {
+            negative,
+            coefficient,
+            exponent,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coefficient == "0"
+    }
+
+    /// The value as an `i64`, or `None` if it is non-integral or out of range.
+    pub fn as_i64(&self) -> Option<i64> {
+        if self.is_zero() {
+            return Some(0);
+        }
+        if self.exponent < 0 {
+            return None;
+        }
+        let mut text = String::with_capacity(self.coefficient.len() + self.exponent as usize + 1);
+        if self.negative {
+            text.push('-');
+        }
+        text.push_str(&self.coefficient);
+        for _ in 0..self.exponent {
+            text.push('0');
+        }
+        text.parse().ok()
+    }
+
+    /// The value as an `f64`. This may lose precision for values outside the
+    /// `f64` mantissa, but never fails.
+    pub fn as_f64(&self) -> f64 {
+        self.as_str().parse().unwrap_or(f64::NAN)
+    }
+
+    /// The lossless canonical string form, e.g. `-1.25` or `123e45`.
+    pub fn as_str(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let mut out = String::new();
+        if self.negative {
+            out.push('-');
+        }
+        if self.exponent >= 0 {
+            out.push_str(&self.coefficient);
+            if self.exponent > 0 {
+                out.push('e');
+                out.push_str(&self.exponent.to_string());
+            }
+        } else {
+            let point = self.coefficient.len() as i64 + self.exponent;
+            if point <= 0 {
+                out.push_str("0.");
+                for _ in 0..-point {
+                    out.push('0');
+                }
+                out.push_str(&self.coefficient);
+            } else {
+                let (int_part, frac_part) = self.coefficient.split_at(point as usize);
+                out.push_str(int_part);
+                out.push('.');
+                out.push_str(frac_part);
+            }
+        }
+        out
+    }
+}
+
+impl PartialEq for PreciseNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.negative == other.negative
+            && self.exponent == other.exponent
+            && self.coefficient == other.coefficient
+    }
+}
+
+impl Eq for PreciseNumber {}
+
+impl std::hash::Hash for PreciseNumber {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.negative.hash(state);
+        self.exponent.hash(state);
+        self.coefficient.hash(state);
+    }
+}
+
+impl std::fmt::Display for PreciseNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str())
+    }
+}
+
+impl kson_value::KsonNumber {
+    /// Recover an arbitrary-precision view of this number. The integer variant
+    /// is exact; the decimal variant is reconstructed from its `f64`, so it is
+    /// only as precise as the JVM-side value.
+    pub fn to_precise(&self) -> PreciseNumber {
+        match self {
+            kson_value::KsonNumber::Integer(i) => {
+                PreciseNumber::parse(&i.value().to_string()).unwrap_or_else(zero)
+            }
+            kson_value::KsonNumber::Decimal(d) => {
+                PreciseNumber::parse(&format!("{}", d.value())).unwrap_or_else(zero)
+            }
+        }
+    }
+}
+
+impl KsonValue {
+    /// Recover an arbitrary-precision view of this node if it is a number.
+    pub fn as_precise_number(&self) -> Option<PreciseNumber> {
+        match self {
+            KsonValue::KsonNumber(n) => Some(n.to_precise()),
+            _ => None,
+        }
+    }
+}
+
+fn zero() -> PreciseNumber {
+    PreciseNumber::normalized(false, "0".to_string(), 0)
+}
@@ -0,0 +1,159 @@
+//! A tiny jq-like path evaluator returning nodes with their spans.
+//!
+//! [`KsonValue::query_expr`] compiles a small path expression into a sequence of
+//! selector steps and evaluates them as a worklist: it starts from
+//! `vec![self]` and, for each step, maps the current set of nodes to the next.
+//! Because evaluation returns the original JVM-backed [`KsonValue`] nodes, every
+//! match keeps its `start()`/`end()` [`Position`](crate::Position)s so
+//! editor/LSP tooling can jump straight to it.
+//!
+//! It is named `query_expr` to coexist with the [`query`](crate::path_query) and
+//! [`select`](crate::jq) path APIs already on [`KsonValue`].
+//!
+//! # Steps
+//!
+//! - `.foo` / `foo` — the value of key `foo` in a `KsonObject`.
+//! - `[n]` — element `n` of a `KsonArray`.
+//! - `[]` / `.*` — every child (array elements, object values).
+//! - `..` — recursive descent: every descendant, including the current node.
+//!
+//! Missing keys and out-of-range indices yield no match rather than an error,
+//! and steps applied to a node of the wrong `type_()` drop it silently.
+
+use crate::KsonValue;
+
+enum Step {
+    Field(String),
+    Index(usize),
+    Wildcard,
+    Descend,
+}
+
+impl KsonValue {
+    /// Evaluate a jq-like path expression, returning every matched node in
+    /// document order. Malformed tokens are skipped; no error is produced.
+    pub fn query_expr(&self, expr: &str) -> Vec<KsonValue> {
+        let steps = parse(expr);
+        let mut current = vec![self.clone()];
+        for step in &steps {
+            let mut next = Vec::new();
+            for node in &current {
+                step_into(step, node, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+fn parse(expr: &str) -> Vec<Step> {
+    let bytes = expr.as_bytes();
+    let mut steps = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'.' => {
+                if bytes.get(i + 1) == Some(&b'.') {
+                    steps.push(Step::Descend);
+                    i += 2;
+                } else if bytes.get(i + 1) == Some(&b'*') {
+                    steps.push(Step::Wildcard);
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            b'[' => {
+                if let Some(offset) = expr[i..].find(']') {
+                    let inner = expr[i + 1..i + offset].trim();
+                    if inner.is_empty() || inner == "*" {
+                        steps.push(Step::Wildcard);
+                    } else if let Ok(index) = inner.parse::<usize>() {
+                        steps.push(Step::Index(index));
+                    }
+                    i += offset + 1;
+                } else {
+                    break;
+                }
+            }
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                let start = i;
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                steps.push(Step::Field(expr[start..i].to_string()));
+            }
+            _ => i += 1,
+        }
+    }
+    steps
+}
+
+fn step_into(step: &Step, node: &KsonValue, out: &mut Vec<KsonValue>) {
+    match step {
+        Step::Field(name) => {
+            if let KsonValue::KsonObject(object) = node {
+                if let Some(value) = object.properties().get(name) {
+                    out.push(value.clone());
+                }
+            }
+        }
+        Step::Index(index) => {
+            if let KsonValue::KsonArray(array) = node {
+                let elements = array.elements();
+                if let Some(value) = elements.get(*index) {
+                    out.push(value.clone());
+                }
+            }
+        }
+        Step::Wildcard => match node {
+            KsonValue::KsonArray(array) => out.extend(array.elements()),
+            KsonValue::KsonObject(object) => out.extend(ordered_values(object)),
+            _ => {}
+        },
+        Step::Descend => descend(node, out),
+    }
+}
+
+fn descend(node: &KsonValue, out: &mut Vec<KsonValue>) {
+    out.push(node.clone());
+    match node {
+        KsonValue::KsonArray(array) => {
+            for element in array.elements() {
+                descend(&element, out);
+            }
+        }
+        KsonValue::KsonObject(object) => {
+            for value in ordered_values(object) {
+                descend(&value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An object's values in document order — sorted by the source `start()`
+/// position of each key, since `properties()` iterates the backing `HashMap`
+/// arbitrarily.
+fn ordered_values(object: &crate::kson_value::KsonObject) -> Vec<KsonValue> {
+    let positions: std::collections::HashMap<String, (i32, i32)> = object
+        .property_keys()
+        .into_iter()
+        .map(|(name, key)| {
+            let start = key.start();
+            (name, (start.line(), start.column()))
+        })
+        .collect();
+    let mut keyed: Vec<(String, KsonValue)> = object.properties().into_iter().collect();
+    keyed.sort_by_key(|(key, _)| {
+        positions
+            .get(key)
+            .copied()
+            .unwrap_or((i32::MAX, i32::MAX))
+    });
+    keyed.into_iter().map(|(_, value)| value).collect()
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+}
@@ -0,0 +1,191 @@
+//! Offset-based incremental re-analysis for editor integration.
+//!
+//! [`Kson::analyze`] re-lexes and re-validates the whole document on every call.
+//! Editors that re-run it per keystroke instead want to describe the single
+//! [`TextEdit`] that happened and repaint only what changed. [`Analysis::reanalyze`]
+//! reconstructs the document from the previous token stream, applies the edit,
+//! and — in the spirit of tree-sitter's incremental lexing — keeps the tokens
+//! before the edit verbatim, shifts the tokens after it by the edit's length
+//! delta, and resynchronizes with the freshly lexed middle, returning a
+//! [`Reanalysis`] that carries the new token stream plus the byte ranges a
+//! caller needs to repaint.
+//!
+//! The Kotlin tokenizer only exposes whole-document lexing, so the freshly lexed
+//! stream is produced by one [`Kson::analyze`] call; the incremental payoff is
+//! the minimal *changed-range* set, computed by matching the unchanged prefix
+//! and (shifted) suffix against the old stream. When the token straddling the
+//! edit is a state-bearing embed/string body — where lexer state cannot be
+//! proven equivalent at the boundary — the whole document is reported as
+//! changed, the safe fall-back the invariant demands.
+
+use std::ops::Range;
+
+use crate::{Analysis, Kson, Token, TokenType};
+
+/// A single document edit: the half-open byte range `[start, end)` that was
+/// replaced, and the text spliced in its place.
+#[derive(Clone)]
+pub struct TextEdit {
+    start: usize,
+    end: usize,
+    replacement: String,
+}
+
+impl TextEdit {
+    /// An edit replacing bytes `start..end` with `replacement`.
+    pub fn new(start: usize, end: usize, replacement: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// The result of [`Analysis::reanalyze`]: the new token stream and the byte
+/// ranges (in the post-edit source) whose tokens differ from before.
+pub struct Reanalysis {
+    tokens: Vec<Token>,
+    changed: Vec<Range<usize>>,
+}
+
+impl Reanalysis {
+    /// The full post-edit token stream.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// The byte ranges a caller needs to repaint, in source order.
+    pub fn changed_ranges(&self) -> &[Range<usize>] {
+        &self.changed
+    }
+}
+
+impl Analysis {
+    /// Re-analyze this document after `edit`, reusing the unaffected tokens and
+    /// reporting the changed byte ranges. See the [module docs](self).
+    pub fn reanalyze(&self, edit: &TextEdit) -> Reanalysis {
+        let old_tokens = self.tokens();
+
+        // Reconstruct the old source and each token's absolute byte span by
+        // concatenating token texts (whitespace is tokenized, so this is exact).
+        let mut old_offsets: Vec<Range<usize>> = Vec::with_capacity(old_tokens.len());
+        let mut old_source = String::new();
+        for token in &old_tokens {
+            let text = token.text();
+            let start = old_source.len();
+            old_source.push_str(&text);
+            old_offsets.push(start..old_source.len());
+        }
+
+        let start = edit.start.min(old_source.len());
+        let end = edit.end.clamp(start, old_source.len());
+        let delta = edit.replacement.len() as isize - (end - start) as isize;
+
+        let mut new_source = String::with_capacity(old_source.len());
+        new_source.push_str(&old_source[..start]);
+        new_source.push_str(&edit.replacement);
+        new_source.push_str(&old_source[end..]);
+
+        let new_tokens = Kson::analyze(&new_source, None).tokens();
+
+        // If the edit falls inside a state-bearing body token, lexer state at the
+        // boundary is not provably equivalent — report the whole document.
+        if straddles_stateful(&old_tokens, &old_offsets, start, end) {
+            let changed = if new_source.is_empty() {
+                Vec::new()
+            } else {
+                vec![0..new_source.len()]
+            };
+            return Reanalysis {
+                tokens: new_tokens,
+                changed,
+            };
+        }
+
+        // Unchanged prefix: tokens wholly before the edit that match by type and
+        // offset in the new stream.
+        let mut prefix = 0;
+        while prefix < old_tokens.len()
+            && prefix < new_tokens.len()
+            && old_offsets[prefix].end <= start
+            && same_token(&old_tokens[prefix], &new_tokens[prefix], old_offsets[prefix].start, &new_tokens, prefix)
+        {
+            prefix += 1;
+        }
+
+        // Resync suffix: walk from the ends, matching old tokens (shifted by
+        // delta) against new tokens until they diverge.
+        let mut suffix = 0;
+        while suffix < old_tokens.len().saturating_sub(prefix)
+            && suffix < new_tokens.len().saturating_sub(prefix)
+        {
+            let old_index = old_tokens.len() - 1 - suffix;
+            let new_index = new_tokens.len() - 1 - suffix;
+            let shifted = (old_offsets[old_index].start as isize + delta) as usize;
+            if old_offsets[old_index].start < end
+                || new_offset(&new_tokens, new_index) != shifted
+                || old_tokens[old_index].token_type() as u8
+                    != new_tokens[new_index].token_type() as u8
+            {
+                break;
+            }
+            suffix += 1;
+        }
+
+        // The changed window is everything between the matched prefix and suffix.
+        let changed_start = new_tokens.get(prefix).map(|_| new_offset(&new_tokens, prefix));
+        let last_changed = new_tokens.len().saturating_sub(suffix);
+        let changed = match (changed_start, last_changed > prefix) {
+            (Some(begin), true) => {
+                let end = token_end_offset(&new_source, &new_tokens, last_changed - 1);
+                vec![begin..end.max(begin)]
+            }
+            _ => Vec::new(),
+        };
+
+        Reanalysis {
+            tokens: new_tokens,
+            changed,
+        }
+    }
+}
+
+/// Whether the edit `[start, end)` lands inside a state-bearing body token
+/// (embed or string content) of the old stream.
+fn straddles_stateful(
+    tokens: &[Token],
+    offsets: &[Range<usize>],
+    start: usize,
+    end: usize,
+) -> bool {
+    tokens.iter().zip(offsets).any(|(token, span)| {
+        let stateful = matches!(
+            token.token_type(),
+            TokenType::EmbedContent | TokenType::StringContent
+        );
+        stateful && span.start < end && start < span.end
+    })
+}
+
+/// Byte start offset of the `index`th new token, recomputed from text lengths.
+fn new_offset(tokens: &[Token], index: usize) -> usize {
+    tokens[..index].iter().map(|t| t.text().len()).sum()
+}
+
+/// Byte end offset of the `index`th new token within `source`.
+fn token_end_offset(source: &str, tokens: &[Token], index: usize) -> usize {
+    let start: usize = tokens[..index].iter().map(|t| t.text().len()).sum();
+    (start + tokens[index].text().len()).min(source.len())
+}
+
+/// Whether an old token matches a new token by type and absolute start offset.
+fn same_token(
+    old: &Token,
+    new: &Token,
+    old_start: usize,
+    new_tokens: &[Token],
+    new_index: usize,
+) -> bool {
+    old.token_type() as u8 == new.token_type() as u8 && old_start == new_offset(new_tokens, new_index)
+}
@@ -0,0 +1,162 @@
+//! Compiler-style annotated source rendering for diagnostics.
+//!
+//! [`Failure::errors`](crate::result::Failure::errors) hands back a flat
+//! `Vec<Message>`, each carrying a [`MessageSeverity`], a `start`/`end`
+//! [`Position`], and text — but the caller has to build the display. This module
+//! renders those into `rustc`-style snippets: a `error:`/`warning:` header, a
+//! `--> line:column` locator, the offending source line with a line-number
+//! gutter and `|` border, and an underline row of `^` spanning the span.
+
+use crate::{result, schema_result, Message, MessageSeverity, Position};
+
+impl Message {
+    /// Render this message as a compiler-style annotated snippet against
+    /// `source`. If the message's span points outside `source`, only the header
+    /// and locator are emitted.
+    pub fn render(&self, source: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        render_message(self, &lines)
+    }
+}
+
+impl result::Failure {
+    /// Render every error as an annotated snippet, ordered by source position.
+    pub fn render_report(&self, source: &str) -> String {
+        render_report(self.errors(), source)
+    }
+}
+
+impl schema_result::Failure {
+    /// Render every error as an annotated snippet, ordered by source position.
+    pub fn render_report(&self, source: &str) -> String {
+        render_report(self.errors(), source)
+    }
+}
+
+fn render_report(mut messages: Vec<Message>, source: &str) -> String {
+    messages.sort_by_key(|m| {
+        let start = m.start();
+        (start.line(), start.column())
+    });
+    let lines: Vec<&str> = source.lines().collect();
+    messages
+        .iter()
+        .map(|m| render_message(m, &lines))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn severity_label(severity: &MessageSeverity) -> &'static str {
+    match severity {
+        MessageSeverity::Error => "error",
+        MessageSeverity::Warning => "warning",
+    }
+}
+
+fn render_message(message: &Message, lines: &[&str]) -> String {
+    let start = message.start();
+    let end = message.end();
+    let severity = message.severity();
+
+    let mut out = String::new();
+    out.push_str(&format!("{}: {}\n", severity_label(&severity), message.message()));
+    out.push_str(&format!("--> {}:{}\n", start.line(), start.column()));
+
+    // Gracefully skip the source body if the span starts outside `source`.
+    let start_index = match line_index(start.line(), lines) {
+        Some(index) => index,
+        None => return out,
+    };
+    let end_index = line_index(end.line(), lines).unwrap_or(start_index);
+
+    let gutter_width = (end.line().max(start.line())).max(1).to_string().len();
+    let border = format!("{:width$} |", "", width = gutter_width);
+    out.push_str(&border);
+    out.push('\n');
+
+    for index in start_index..=end_index {
+        let line = lines[index];
+        out.push_str(&format!(
+            "{:>width$} | {}\n",
+            index,
+            line,
+            width = gutter_width
+        ));
+
+        let (caret_start, caret_end) = caret_bounds(
+            index,
+            start_index,
+            end_index,
+            start.column(),
+            end.column(),
+            line,
+        );
+        out.push_str(&border);
+        out.push(' ');
+        for _ in 0..caret_start {
+            out.push(' ');
+        }
+        for _ in 0..(caret_end - caret_start).max(1) {
+            out.push('^');
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Resolve a 0-based [`Position`] line to an index into `lines`, returning
+/// `None` when it falls outside the source.
+fn line_index(line: i32, lines: &[&str]) -> Option<usize> {
+    if line < 0 {
+        return None;
+    }
+    let index = line as usize;
+    (index < lines.len()).then_some(index)
+}
+
+/// Compute the `[start, end)` caret column range for `line`, clamped to the
+/// line's length. Interior and trailing lines of a multi-line span underline
+/// from column 0 and/or to end-of-line as appropriate.
+fn caret_bounds(
+    index: usize,
+    start_index: usize,
+    end_index: usize,
+    start_column: i32,
+    end_column: i32,
+    line: &str,
+) -> (usize, usize) {
+    let len = line.chars().count();
+    let clamp = |column: i32| (column.max(0) as usize).min(len);
+
+    let caret_start = if index == start_index { clamp(start_column) } else { 0 };
+    let caret_end = if index == end_index { clamp(end_column) } else { len };
+    (caret_start, caret_end.max(caret_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{caret_bounds, line_index};
+
+    #[test]
+    fn line_index_is_zero_based() {
+        let lines = ["first", "second"];
+        // Line 0 is the first source line, not out of bounds.
+        assert_eq!(line_index(0, &lines), Some(0));
+        assert_eq!(line_index(1, &lines), Some(1));
+    }
+
+    #[test]
+    fn line_index_rejects_negative_and_past_end() {
+        let lines = ["only"];
+        assert_eq!(line_index(-1, &lines), None);
+        assert_eq!(line_index(1, &lines), None);
+    }
+
+    #[test]
+    fn caret_bounds_clamp_to_line_length() {
+        // 0-based columns, clamped to the line's character count.
+        assert_eq!(caret_bounds(0, 0, 0, 5, 16, "key: [1, 2, 3, 4"), (5, 16));
+        assert_eq!(caret_bounds(0, 0, 0, 0, 99, "abc"), (0, 3));
+    }
+}
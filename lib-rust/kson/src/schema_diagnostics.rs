@@ -0,0 +1,122 @@
+//! Typed schema-validation diagnostics.
+//!
+//! [`SchemaValidatorService::validate`] hands back flat [`Message`]s whose only
+//! machine-readable fields are the formatted English text, a
+//! [`MessageSeverity`], and a source span — the JVM binding exposes no typed
+//! error, instance path, or failed-keyword. [`SchemaDiagnostic`] layers the
+//! small amount of structure that can be recovered faithfully: it preserves the
+//! severity and span, and decodes the one stable message form the validator is
+//! known to emit — a type mismatch, `"Expected one of: string, but got:
+//! integer"` — into [`DiagnosticKind::TypeMismatch`]. Every other message is
+//! kept verbatim as [`DiagnosticKind::Other`] rather than scraped against
+//! guessed wording.
+//!
+//! Richer structure (a JSON-pointer instance path, the failed keyword,
+//! `minimum`/`items`/… operands) requires the Kotlin `SchemaValidatorService` to
+//! surface a typed error across the FFI boundary; until it does, this module
+//! does not invent that structure from the display strings.
+
+use crate::{Message, MessageSeverity, Position, SchemaValidatorService};
+
+/// A single validation failure, decoded from a [`Message`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDiagnostic {
+    /// The structured failure, as far as it can be recovered from the message.
+    pub kind: DiagnosticKind,
+    /// Severity carried over from the underlying [`Message`].
+    pub severity: MessageSeverity,
+    /// Start of the offending span.
+    pub start: Position,
+    /// End of the offending span.
+    pub end: Position,
+}
+
+/// The decoded shape of a validation failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A value's type did not match the schema (`type` keyword), decoded from
+    /// the validator's `"Expected one of: …, but got: …"` message.
+    TypeMismatch { expected: Vec<String>, found: String },
+    /// Any failure whose message does not match a form this module decodes; the
+    /// original validator text is preserved unchanged.
+    Other { message: String },
+}
+
+impl DiagnosticKind {
+    /// The schema keyword this diagnostic is attributed to, or `""` when the
+    /// message could not be decoded to a specific keyword.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            DiagnosticKind::TypeMismatch { .. } => "type",
+            DiagnosticKind::Other { .. } => "",
+        }
+    }
+}
+
+impl SchemaValidatorService {
+    /// Validate `input` and return structured diagnostics.
+    ///
+    /// This wraps [`SchemaValidatorService::validate`] and decodes each
+    /// [`Message`] into a [`SchemaDiagnostic`], keeping the original span.
+    pub fn validate_typed(&self, input: &str) -> Vec<SchemaDiagnostic> {
+        self.validate(input)
+            .iter()
+            .map(SchemaDiagnostic::from_message)
+            .collect()
+    }
+}
+
+impl SchemaDiagnostic {
+    /// Decode a validator [`Message`] into a structured diagnostic.
+    pub fn from_message(message: &Message) -> Self {
+        SchemaDiagnostic {
+            kind: classify(&message.message()),
+            severity: message.severity(),
+            start: message.start(),
+            end: message.end(),
+        }
+    }
+}
+
+fn classify(body: &str) -> DiagnosticKind {
+    // "Expected one of: string, boolean, but got: integer"
+    if let Some(rest) = body.strip_prefix("Expected one of: ") {
+        if let Some((expected, found)) = rest.split_once(", but got: ") {
+            return DiagnosticKind::TypeMismatch {
+                expected: split_list(expected),
+                found: found.trim().to_string(),
+            };
+        }
+    }
+    DiagnosticKind::Other {
+        message: body.to_string(),
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify, DiagnosticKind};
+
+    #[test]
+    fn classifies_type_mismatch() {
+        assert_eq!(
+            classify("Expected one of: string, boolean, but got: integer"),
+            DiagnosticKind::TypeMismatch {
+                expected: vec!["string".into(), "boolean".into()],
+                found: "integer".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn unrecognized_messages_fall_back_to_other() {
+        assert_eq!(
+            classify("something unexpected"),
+            DiagnosticKind::Other { message: "something unexpected".into() }
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! LSP `textDocument/semanticTokens/full` encoding over [`Analysis::tokens`].
+//!
+//! Language servers built on this crate would otherwise have to hand-roll a
+//! mapping from [`TokenType`] to highlight scopes. [`Analysis::semantic_tokens`]
+//! emits the delta-encoded `u32` array the LSP protocol expects — one
+//! `(deltaLine, deltaStartChar, length, tokenType, tokenModifiers)` 5-tuple per
+//! visible token — and [`semantic_tokens_legend`] returns the ordered type and
+//! modifier names the indices refer to, so a server can wire up highlighting
+//! without a bespoke translation layer. Whitespace and EOF tokens are skipped.
+
+use crate::{Analysis, TokenType};
+
+/// The ordered token-type and modifier names a [`semantic_tokens`] index array
+/// refers to, matching the LSP `SemanticTokensLegend` shape.
+///
+/// [`semantic_tokens`]: Analysis::semantic_tokens
+pub struct SemanticTokensLegend {
+    /// Token-type names, indexed by the `tokenType` field of each tuple.
+    pub token_types: Vec<String>,
+    /// Token-modifier names, indexed bitwise by the `tokenModifiers` field.
+    pub token_modifiers: Vec<String>,
+}
+
+// Indices into `SemanticTokensLegend::token_types`; kept in sync with
+// `semantic_tokens_legend` and `token_type_index`.
+const TYPE_COMMENT: u32 = 0;
+const TYPE_STRING: u32 = 1;
+const TYPE_NUMBER: u32 = 2;
+const TYPE_KEYWORD: u32 = 3;
+const TYPE_OPERATOR: u32 = 4;
+const TYPE_TYPE: u32 = 5;
+
+/// The legend mapping our [`TokenType`] variants onto standard LSP scopes.
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            "comment".to_string(),
+            "string".to_string(),
+            "number".to_string(),
+            "keyword".to_string(),
+            "operator".to_string(),
+            "type".to_string(),
+        ],
+        token_modifiers: Vec::new(),
+    }
+}
+
+/// The LSP type index for `token_type`, or `None` for tokens that are not
+/// emitted (whitespace and EOF).
+fn token_type_index(token_type: TokenType) -> Option<u32> {
+    let index = match token_type {
+        TokenType::Comment => TYPE_COMMENT,
+        TokenType::UnquotedString
+        | TokenType::StringOpenQuote
+        | TokenType::StringCloseQuote
+        | TokenType::StringContent
+        | TokenType::EmbedContent => TYPE_STRING,
+        TokenType::Number => TYPE_NUMBER,
+        TokenType::True | TokenType::False | TokenType::Null => TYPE_KEYWORD,
+        TokenType::EmbedTag => TYPE_TYPE,
+        TokenType::CurlyBraceL
+        | TokenType::CurlyBraceR
+        | TokenType::SquareBracketL
+        | TokenType::SquareBracketR
+        | TokenType::AngleBracketL
+        | TokenType::AngleBracketR
+        | TokenType::Colon
+        | TokenType::Dot
+        | TokenType::EndDash
+        | TokenType::Comma
+        | TokenType::ListDash
+        | TokenType::EmbedOpenDelim
+        | TokenType::EmbedCloseDelim
+        | TokenType::EmbedPreambleNewline
+        | TokenType::IllegalChar => TYPE_OPERATOR,
+        TokenType::Whitespace | TokenType::Eof => return None,
+    };
+    Some(index)
+}
+
+impl Analysis {
+    /// Encode this document's tokens as the LSP semantic-tokens `data` array:
+    /// a flat sequence of `(deltaLine, deltaStartChar, length, tokenType,
+    /// tokenModifiers)` 5-tuples, each relative to the previously emitted token.
+    pub fn semantic_tokens(&self) -> Vec<u32> {
+        let mut data = Vec::new();
+        let mut prev_line = 0i32;
+        let mut prev_char = 0i32;
+
+        for token in self.tokens() {
+            let Some(type_index) = token_type_index(token.token_type()) else {
+                continue;
+            };
+
+            let start = token.start();
+            let end = token.end();
+            let line = start.line();
+            let char_start = start.column();
+            let length = token_length(&token.text(), line, char_start, end.line(), end.column());
+
+            let delta_line = (line - prev_line).max(0) as u32;
+            let delta_char = if delta_line == 0 {
+                (char_start - prev_char).max(0) as u32
+            } else {
+                char_start.max(0) as u32
+            };
+
+            data.extend_from_slice(&[delta_line, delta_char, length, type_index, 0]);
+            prev_line = line;
+            prev_char = char_start;
+        }
+
+        data
+    }
+}
+
+/// The highlighted length of a token. LSP semantic tokens are single-line, so a
+/// token whose span crosses a line boundary is measured up to its first newline.
+fn token_length(text: &str, start_line: i32, start_col: i32, end_line: i32, end_col: i32) -> u32 {
+    if start_line == end_line {
+        (end_col - start_col).max(0) as u32
+    } else {
+        text.split('\n').next().unwrap_or(text).chars().count() as u32
+    }
+}
@@ -0,0 +1,297 @@
+//! `serde` impls for the individual [`KsonValue`] family members.
+//!
+//! [`KsonValue`] itself is serde-capable via [`serde_support`](crate::serde_support),
+//! but the concrete node types — [`KsonArray`](kson_value::KsonArray),
+//! [`KsonBoolean`](kson_value::KsonBoolean), [`KsonEmbed`](kson_value::KsonEmbed),
+//! [`KsonNull`](kson_value::KsonNull), [`KsonString`](kson_value::KsonString),
+//! [`KsonObject`](kson_value::KsonObject), and
+//! [`kson_number::Decimal`]/[`kson_number::Integer`] — also need to flow through
+//! serde pipelines (`serde_json`, messagepack, config loaders) on their own.
+//!
+//! Serialization emits the natural JSON-ish shape; deserialization rebuilds the
+//! JVM-backed objects through the existing `new(...)` constructors, synthesizing
+//! placeholder [`Position`] spans since external formats carry no source info.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{kson_value, KsonValue, Position};
+
+fn span() -> Position {
+    Position::new(0, 0)
+}
+
+// ---------------------------------------------------------------------------
+// Serialize
+// ---------------------------------------------------------------------------
+
+impl Serialize for kson_value::KsonNull {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+impl Serialize for kson_value::KsonBoolean {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(self.value())
+    }
+}
+
+impl Serialize for kson_value::KsonString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.value())
+    }
+}
+
+impl Serialize for kson_value::kson_number::Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.value() as i64)
+    }
+}
+
+impl Serialize for kson_value::kson_number::Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.value())
+    }
+}
+
+impl Serialize for kson_value::KsonNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            kson_value::KsonNumber::Integer(i) => i.serialize(serializer),
+            kson_value::KsonNumber::Decimal(d) => d.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for kson_value::KsonArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let elements = self.elements();
+        let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+        for element in &elements {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for kson_value::KsonObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let properties = self.properties();
+        let mut map = serializer.serialize_map(Some(properties.len()))?;
+        for (key, value) in &properties {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for kson_value::KsonEmbed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("tag", &self.tag())?;
+        map.serialize_entry("content", &self.content())?;
+        map.end()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Deserialize
+// ---------------------------------------------------------------------------
+
+impl<'de> Deserialize<'de> for kson_value::KsonNull {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonNull;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("null")
+            }
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(kson_value::KsonNull::new(span(), span()))
+            }
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                self.visit_unit()
+            }
+        }
+        deserializer.deserialize_unit(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonBoolean {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonBoolean;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a boolean")
+            }
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+                Ok(kson_value::KsonBoolean::new(v, span(), span()))
+            }
+        }
+        deserializer.deserialize_bool(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonString;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string")
+            }
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(kson_value::KsonString::new(v, span(), span()))
+            }
+        }
+        deserializer.deserialize_string(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::kson_number::Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::kson_number::Integer;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an integer")
+            }
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(kson_value::kson_number::Integer::new(v as i32, span(), span()))
+            }
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                self.visit_i64(v as i64)
+            }
+        }
+        deserializer.deserialize_i64(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::kson_number::Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::kson_number::Decimal;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a decimal")
+            }
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(kson_value::kson_number::Decimal::new(v, span(), span()))
+            }
+        }
+        deserializer.deserialize_f64(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonNumber;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a number")
+            }
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(kson_value::KsonNumber::Integer(
+                    kson_value::kson_number::Integer::new(v as i32, span(), span()),
+                ))
+            }
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                self.visit_i64(v as i64)
+            }
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(kson_value::KsonNumber::Decimal(
+                    kson_value::kson_number::Decimal::new(v, span(), span()),
+                ))
+            }
+        }
+        deserializer.deserialize_any(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonArray;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an array")
+            }
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut elements = Vec::new();
+                while let Some(element) = seq.next_element::<KsonValue>()? {
+                    elements.push(element);
+                }
+                Ok(kson_value::KsonArray::new(&elements, span(), span()))
+            }
+        }
+        deserializer.deserialize_seq(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonObject;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut properties: HashMap<String, KsonValue> = HashMap::new();
+                while let Some((key, value)) = map.next_entry::<String, KsonValue>()? {
+                    properties.insert(key, value);
+                }
+                let borrowed: HashMap<&str, KsonValue> = properties
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.clone()))
+                    .collect();
+                let keys: HashMap<&str, kson_value::KsonString> = properties
+                    .keys()
+                    .map(|k| (k.as_str(), kson_value::KsonString::new(k, span(), span())))
+                    .collect();
+                Ok(kson_value::KsonObject::new(&borrowed, &keys, span(), span()))
+            }
+        }
+        deserializer.deserialize_map(V)
+    }
+}
+
+impl<'de> Deserialize<'de> for kson_value::KsonEmbed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = kson_value::KsonEmbed;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an embed block with `tag` and `content`")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut tag: Option<String> = None;
+                let mut content: Option<String> = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "tag" => tag = map.next_value::<Option<String>>()?,
+                        "content" => content = Some(map.next_value::<String>()?),
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                let content = content.unwrap_or_default();
+                Ok(kson_value::KsonEmbed::new(
+                    tag.as_deref(),
+                    &content,
+                    span(),
+                    span(),
+                ))
+            }
+        }
+        deserializer.deserialize_map(V)
+    }
+}
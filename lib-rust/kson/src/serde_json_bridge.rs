@@ -0,0 +1,143 @@
+//! Conversion between [`KsonValue`] and [`serde_json::Value`].
+//!
+//! This bridges KSON into the broader serde ecosystem used for config loading
+//! and interchange. The mapping is reversible: `KsonNull`/`KsonBoolean`/
+//! `KsonString`/`KsonArray`/`KsonObject` map to their obvious JSON counterparts,
+//! `KsonNumber` preserves the integer-vs-floating distinction, and `KsonEmbed`
+//! is represented as a tagged object `{"$embed": {"tag": …, "content": …}}` so
+//! it round-trips.
+//!
+//! The reverse direction reconstructs nodes through the `kson_value::*::new(...)`
+//! constructors, synthesizing placeholder [`Position`]s since JSON carries no
+//! source spans. This lets a serde-produced document be validated through
+//! [`SchemaValidatorService`](crate::SchemaValidatorService) and printed back
+//! out.
+//!
+//! One caveat on the reverse direction: the JVM integer binding is `i32`-wide,
+//! so a JSON integer outside that range cannot be stored as a KSON integer. It
+//! is represented as a decimal instead of being wrapped to a wrong value, which
+//! preserves magnitude but not the integer type.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Number, Value};
+
+use crate::{kson_value, KsonValue, Position};
+
+/// The key under which an embed block is projected into JSON.
+const EMBED_KEY: &str = "$embed";
+
+fn span() -> Position {
+    Position::new(0, 0)
+}
+
+impl KsonValue {
+    /// Convert this tree into a [`serde_json::Value`].
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            KsonValue::KsonNull(_) => Value::Null,
+            KsonValue::KsonBoolean(b) => Value::Bool(b.value()),
+            KsonValue::KsonString(s) => Value::String(s.value()),
+            KsonValue::KsonNumber(n) => match n {
+                kson_value::KsonNumber::Integer(i) => Value::Number(Number::from(i.value())),
+                kson_value::KsonNumber::Decimal(d) => Number::from_f64(d.value())
+                    .map(Value::Number)
+                    .unwrap_or(Value::Null),
+            },
+            KsonValue::KsonArray(a) => {
+                Value::Array(a.elements().iter().map(KsonValue::to_json_value).collect())
+            }
+            KsonValue::KsonObject(o) => {
+                let mut map = Map::new();
+                for (key, value) in o.properties() {
+                    map.insert(key, value.to_json_value());
+                }
+                Value::Object(map)
+            }
+            KsonValue::KsonEmbed(e) => {
+                let mut embed = Map::new();
+                embed.insert(
+                    "tag".to_string(),
+                    e.tag().map(Value::String).unwrap_or(Value::Null),
+                );
+                embed.insert("content".to_string(), Value::String(e.content()));
+                let mut outer = Map::new();
+                outer.insert(EMBED_KEY.to_string(), Value::Object(embed));
+                Value::Object(outer)
+            }
+        }
+    }
+}
+
+/// Reconstruct a [`KsonValue`] from a [`serde_json::Value`], synthesizing
+/// placeholder [`Position`]s since JSON has no source spans.
+pub fn from_json_value(value: &Value) -> KsonValue {
+    match value {
+        Value::Null => KsonValue::KsonNull(kson_value::KsonNull::new(span(), span())),
+        Value::Bool(b) => {
+            KsonValue::KsonBoolean(kson_value::KsonBoolean::new(*b, span(), span()))
+        }
+        Value::String(s) => {
+            KsonValue::KsonString(kson_value::KsonString::new(s, span(), span()))
+        }
+        Value::Number(n) => number_from_json(n),
+        Value::Array(elements) => {
+            let elements: Vec<KsonValue> = elements.iter().map(from_json_value).collect();
+            KsonValue::KsonArray(kson_value::KsonArray::new(&elements, span(), span()))
+        }
+        Value::Object(map) => {
+            if let Some(embed) = decode_embed(map) {
+                return embed;
+            }
+            object_from_json(map)
+        }
+    }
+}
+
+fn number_from_json(number: &Number) -> KsonValue {
+    // The JVM integer binding is `i32`-wide. Only take the integer path when the
+    // value fits; a larger integer falls through to the floating path rather
+    // than being silently wrapped to a wrong value by an `as i32` cast.
+    if let Some(value) = number.as_i64().and_then(|i| i32::try_from(i).ok()) {
+        KsonValue::KsonNumber(kson_value::KsonNumber::Integer(
+            kson_value::kson_number::Integer::new(value, span(), span()),
+        ))
+    } else {
+        let value = number.as_f64().unwrap_or(f64::NAN);
+        KsonValue::KsonNumber(kson_value::KsonNumber::Decimal(
+            kson_value::kson_number::Decimal::new(value, span(), span()),
+        ))
+    }
+}
+
+fn object_from_json(map: &Map<String, Value>) -> KsonValue {
+    let values: HashMap<&str, KsonValue> = map
+        .iter()
+        .map(|(k, v)| (k.as_str(), from_json_value(v)))
+        .collect();
+    let keys: HashMap<&str, kson_value::KsonString> = map
+        .keys()
+        .map(|k| (k.as_str(), kson_value::KsonString::new(k, span(), span())))
+        .collect();
+    KsonValue::KsonObject(kson_value::KsonObject::new(&values, &keys, span(), span()))
+}
+
+/// Decode a `{"$embed": {"tag": …, "content": …}}` projection back into a
+/// [`KsonValue::KsonEmbed`], or `None` if `map` is a plain object.
+fn decode_embed(map: &Map<String, Value>) -> Option<KsonValue> {
+    if map.len() != 1 {
+        return None;
+    }
+    let embed = map.get(EMBED_KEY)?.as_object()?;
+    let tag = match embed.get("tag") {
+        Some(Value::String(tag)) => Some(tag.as_str()),
+        _ => None,
+    };
+    let content = embed.get("content").and_then(Value::as_str).unwrap_or("");
+    Some(KsonValue::KsonEmbed(kson_value::KsonEmbed::new(
+        tag,
+        content,
+        span(),
+        span(),
+    )))
+}
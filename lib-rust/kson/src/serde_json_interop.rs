@@ -0,0 +1,30 @@
+//! Infallible `From`/`TryFrom` conversions between [`KsonValue`] and
+//! [`serde_json::Value`].
+//!
+//! The [`serde_json_bridge`](crate::serde_json_bridge) module already exposes
+//! the `KsonValue::to_json_value` method and the `from_json_value` function; these impls put the
+//! same mapping behind the standard conversion traits so callers can write
+//! `KsonValue::from(json)` and `serde_json::Value::try_from(value)` (or
+//! `value.try_into()`) when round-tripping through `serde_json`. The `TryFrom`
+//! direction is currently infallible, but is spelled as `TryFrom` so the
+//! signature stays stable if a future, fallible projection is needed.
+
+use std::convert::Infallible;
+
+use serde_json::Value;
+
+use crate::{serde_json_bridge, KsonValue};
+
+impl From<Value> for KsonValue {
+    fn from(value: Value) -> Self {
+        serde_json_bridge::from_json_value(&value)
+    }
+}
+
+impl TryFrom<KsonValue> for Value {
+    type Error = Infallible;
+
+    fn try_from(value: KsonValue) -> Result<Self, Self::Error> {
+        Ok(value.to_json_value())
+    }
+}
@@ -0,0 +1,695 @@
+//! A `serde` data format backed by KSON.
+//!
+//! This module lets Rust types round-trip through KSON the same way the
+//! `Kson::to_json`/`to_yaml` helpers expose string-to-string conversion:
+//! [`from_str`] parses a document and drives a [`serde::Deserializer`] off the
+//! resulting [`KsonValue`] tree, while [`to_string`] walks any `Serialize`able
+//! value straight into KSON text. [`KsonValue`] itself implements
+//! [`serde::Serialize`]/[`serde::Deserialize`] so it can be used as an
+//! intermediate model.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{kson_value, Kson, KsonValue, Position};
+
+/// An error produced while (de)serializing a [`KsonValue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The KSON source could not be parsed into a value tree.
+    Parse(String),
+    /// A value of one shape was found where another was expected.
+    Type { expected: String, found: String },
+    /// A free-form message originating from `serde` itself.
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(msg) => write!(f, "failed to parse kson: {msg}"),
+            Error::Type { expected, found } => {
+                write!(f, "expected {expected}, but got {found}")
+            }
+            Error::Message(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+/// The two ways an embedded block can be represented in the serde model.
+///
+/// No function in this module takes an `EmbedRepr`: [`KsonValue`]'s
+/// `Serialize` impl always emits the `Tagged` form, and [`from_str`] /
+/// [`from_kson_value`] always hand an embed to the visitor as its bare content
+/// string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedRepr {
+    /// Emit embeds as a `{embedTag, embedContent}` map (matches
+    /// `retain_embed_tags = true`).
+    Tagged,
+    /// Emit embeds as their bare content string (matches
+    /// `retain_embed_tags = false`).
+    Content,
+}
+
+impl Default for EmbedRepr {
+    fn default() -> Self {
+        EmbedRepr::Tagged
+    }
+}
+
+/// Deserialize a `T` from KSON source text.
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, Error> {
+    let analysis = Kson::analyze(input, None);
+    let messages = analysis.errors();
+    if !messages.is_empty() {
+        let joined = messages
+            .iter()
+            .map(|m| m.message())
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(Error::Parse(joined));
+    }
+    let value = analysis
+        .kson_value()
+        .ok_or_else(|| Error::Parse("empty document".to_string()))?;
+    from_kson_value(&value)
+}
+
+/// Deserialize a `T` from an already-parsed [`KsonValue`].
+pub fn from_kson_value<T: DeserializeOwned>(value: &KsonValue) -> Result<T, Error> {
+    T::deserialize(ValueDeserializer { value })
+}
+
+/// Serialize a value into KSON text.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    let mut out = String::new();
+    value.serialize(&mut TextSerializer { out: &mut out })?;
+    Ok(out)
+}
+
+/// Serialize any `Serialize`able value into a JVM-backed [`KsonValue`] tree,
+/// ready to hand back to the transpiler or to compare against a parsed
+/// document. The value is buffered through the self-describing
+/// [`serde_json::Value`] model and rebuilt with [`KsonValue`]'s own
+/// [`Deserialize`] impl, so embed blocks keep their tagged
+/// `{embedTag, embedContent}` shape (see [`EmbedRepr`]).
+pub fn to_kson_value<T: Serialize + ?Sized>(value: &T) -> Result<KsonValue, Error> {
+    let buffer = serde_json::to_value(value).map_err(|e| Error::Message(e.to_string()))?;
+    KsonValue::deserialize(buffer).map_err(|e: serde_json::Error| Error::Message(e.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// KsonValue <-> serde
+// ---------------------------------------------------------------------------
+
+impl Serialize for KsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            KsonValue::KsonNull(_) => serializer.serialize_unit(),
+            KsonValue::KsonBoolean(b) => serializer.serialize_bool(b.value()),
+            KsonValue::KsonString(s) => serializer.serialize_str(&s.value()),
+            KsonValue::KsonNumber(n) => match n {
+                kson_value::KsonNumber::Integer(i) => serializer.serialize_i64(i.value() as i64),
+                kson_value::KsonNumber::Decimal(d) => serializer.serialize_f64(d.value()),
+            },
+            KsonValue::KsonArray(a) => {
+                let elements = a.elements();
+                let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+                for element in &elements {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+            KsonValue::KsonObject(o) => {
+                let properties = o.properties();
+                let mut map = serializer.serialize_map(Some(properties.len()))?;
+                for (key, value) in &properties {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            KsonValue::KsonEmbed(e) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("embedTag", &e.tag())?;
+                map.serialize_entry("embedContent", &e.content())?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KsonValueVisitor)
+    }
+}
+
+struct KsonValueVisitor;
+
+fn span() -> Position {
+    Position::new(0, 0)
+}
+
+impl<'de> Visitor<'de> for KsonValueVisitor {
+    type Value = KsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any valid KSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<KsonValue, E> {
+        Ok(KsonValue::KsonBoolean(kson_value::KsonBoolean::new(
+            v,
+            span(),
+            span(),
+        )))
+    }
+
+    // The JVM integer binding is `i32`-wide, so integers outside that range
+    // are rejected rather than silently wrapped into a wrong value.
+    fn visit_i64<E>(self, v: i64) -> Result<KsonValue, E>
+    where
+        E: de::Error,
+    {
+        let value = i32::try_from(v).map_err(|_| {
+            E::custom(format!("integer {v} is out of range for KSON's 32-bit integers"))
+        })?;
+        Ok(KsonValue::KsonNumber(kson_value::KsonNumber::Integer(
+            kson_value::kson_number::Integer::new(value, span(), span()),
+        )))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<KsonValue, E>
+    where
+        E: de::Error,
+    {
+        let value = i32::try_from(v).map_err(|_| {
+            E::custom(format!("integer {v} is out of range for KSON's 32-bit integers"))
+        })?;
+        Ok(KsonValue::KsonNumber(kson_value::KsonNumber::Integer(
+            kson_value::kson_number::Integer::new(value, span(), span()),
+        )))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<KsonValue, E> {
+        Ok(KsonValue::KsonNumber(kson_value::KsonNumber::Decimal(
+            kson_value::kson_number::Decimal::new(v, span(), span()),
+        )))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<KsonValue, E> {
+        Ok(KsonValue::KsonString(kson_value::KsonString::new(
+            v,
+            span(),
+            span(),
+        )))
+    }
+
+    fn visit_unit<E>(self) -> Result<KsonValue, E> {
+        Ok(KsonValue::KsonNull(kson_value::KsonNull::new(span(), span())))
+    }
+
+    fn visit_none<E>(self) -> Result<KsonValue, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<KsonValue, D::Error> {
+        Deserialize::deserialize(d)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KsonValue, A::Error> {
+        let mut elements = Vec::new();
+        while let Some(element) = seq.next_element::<KsonValue>()? {
+            elements.push(element);
+        }
+        Ok(KsonValue::KsonArray(kson_value::KsonArray::new(
+            &elements,
+            span(),
+            span(),
+        )))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<KsonValue, A::Error> {
+        let mut properties: HashMap<String, KsonValue> = HashMap::new();
+        while let Some((key, value)) = map.next_entry::<String, KsonValue>()? {
+            properties.insert(key, value);
+        }
+        let borrowed: HashMap<&str, KsonValue> =
+            properties.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+        let keys: HashMap<&str, kson_value::KsonString> = properties
+            .keys()
+            .map(|k| (k.as_str(), kson_value::KsonString::new(k, span(), span())))
+            .collect();
+        Ok(KsonValue::KsonObject(kson_value::KsonObject::new(
+            &borrowed, &keys, span(), span(),
+        )))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Deserializer driven off a &KsonValue
+// ---------------------------------------------------------------------------
+
+struct ValueDeserializer<'a> {
+    value: &'a KsonValue,
+}
+
+impl<'de, 'a> Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            KsonValue::KsonNull(_) => visitor.visit_unit(),
+            KsonValue::KsonBoolean(b) => visitor.visit_bool(b.value()),
+            KsonValue::KsonString(s) => visitor.visit_string(s.value()),
+            KsonValue::KsonEmbed(e) => visitor.visit_string(e.content()),
+            KsonValue::KsonNumber(n) => match n {
+                kson_value::KsonNumber::Integer(i) => visitor.visit_i64(i.value() as i64),
+                kson_value::KsonNumber::Decimal(d) => visitor.visit_f64(d.value()),
+            },
+            KsonValue::KsonArray(a) => {
+                let elements = a.elements();
+                visitor.visit_seq(SeqDeserializer {
+                    iter: elements.into_iter(),
+                })
+            }
+            KsonValue::KsonObject(o) => {
+                let mut entries: Vec<(String, KsonValue)> = o.properties().into_iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                visitor.visit_map(MapDeserializer {
+                    iter: entries.into_iter(),
+                    value: None,
+                })
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            KsonValue::KsonNull(_) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            KsonValue::KsonString(s) => visitor.visit_enum(s.value().into_deserializer()),
+            _ => Err(Error::Type {
+                expected: "enum".to_string(),
+                found: self.value.type_().name(),
+            }),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf unit unit_struct newtype_struct seq tuple tuple_struct map
+        struct identifier ignored_any
+    }
+}
+
+struct SeqDeserializer {
+    iter: std::vec::IntoIter<KsonValue>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(ValueDeserializer { value: &value }).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapDeserializer {
+    iter: std::vec::IntoIter<(String, KsonValue)>,
+    value: Option<KsonValue>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer { value: &value })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Serializer emitting KSON (JSON-compatible) text
+// ---------------------------------------------------------------------------
+
+struct TextSerializer<'a> {
+    out: &'a mut String,
+}
+
+fn write_escaped(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl<'a, 'b> Serializer for &'b mut TextSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = TextSeq<'a, 'b>;
+    type SerializeTuple = TextSeq<'a, 'b>;
+    type SerializeTupleStruct = TextSeq<'a, 'b>;
+    type SerializeTupleVariant = TextSeq<'a, 'b>;
+    type SerializeMap = TextMap<'a, 'b>;
+    type SerializeStruct = TextMap<'a, 'b>;
+    type SerializeStructVariant = TextMap<'a, 'b>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push_str(if v { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        write_escaped(self.out, &v.to_string());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        write_escaped(self.out, v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        use serde::ser::SerializeSeq as _;
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for byte in v {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push_str("null");
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.out.push('{');
+        write_escaped(self.out, variant);
+        self.out.push(':');
+        value.serialize(&mut TextSerializer { out: self.out })?;
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<TextSeq<'a, 'b>, Error> {
+        self.out.push('[');
+        Ok(TextSeq {
+            ser: self,
+            first: true,
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<TextSeq<'a, 'b>, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<TextSeq<'a, 'b>, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<TextSeq<'a, 'b>, Error> {
+        self.out.push('{');
+        write_escaped(self.out, variant);
+        self.out.push(':');
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<TextMap<'a, 'b>, Error> {
+        self.out.push('{');
+        Ok(TextMap {
+            ser: self,
+            first: true,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<TextMap<'a, 'b>, Error> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<TextMap<'a, 'b>, Error> {
+        self.out.push('{');
+        write_escaped(self.out, variant);
+        self.out.push(':');
+        self.serialize_map(Some(len))
+    }
+}
+
+struct TextSeq<'a, 'b> {
+    ser: &'b mut TextSerializer<'a>,
+    first: bool,
+}
+
+impl<'a, 'b> SerializeSeq for TextSeq<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        if !self.first {
+            self.ser.out.push(',');
+        }
+        self.first = false;
+        value.serialize(&mut TextSerializer { out: self.ser.out })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.ser.out.push(']');
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for TextSeq<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for TextSeq<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeTupleVariant for TextSeq<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.ser.out.push(']');
+        self.ser.out.push('}');
+        Ok(())
+    }
+}
+
+struct TextMap<'a, 'b> {
+    ser: &'b mut TextSerializer<'a>,
+    first: bool,
+}
+
+impl<'a, 'b> SerializeMap for TextMap<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        if !self.first {
+            self.ser.out.push(',');
+        }
+        self.first = false;
+        key.serialize(&mut TextSerializer { out: self.ser.out })
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.ser.out.push(':');
+        value.serialize(&mut TextSerializer { out: self.ser.out })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.ser.out.push('}');
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStruct for TextMap<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.serialize_key(key)?;
+        self.serialize_value(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        SerializeMap::end(self)
+    }
+}
+
+impl<'a, 'b> ser::SerializeStructVariant for TextMap<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.serialize_key(key)?;
+        self.serialize_value(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.ser.out.push('}');
+        self.ser.out.push('}');
+        Ok(())
+    }
+}
@@ -16,6 +16,22 @@ fn test_kson_format() {
     ");
 }
 
+#[test]
+fn test_kson_try_format_ok() {
+    let indent = IndentType::Spaces(IndentTypeSpaces::new(2));
+    let result = Kson::try_format(
+        "key: [1, 2, 3, 4]",
+        &FormatOptions::new(&indent, &FormattingStyle::Plain),
+    );
+    insta::assert_snapshot!(result.unwrap(), @r"
+    key:
+      - 1
+      - 2
+      - 3
+      - 4
+    ");
+}
+
 #[test]
 fn test_kson_format_classic() {
     let indent = IndentType::Spaces(IndentTypeSpaces::new(2));
@@ -167,6 +183,37 @@ fn test_kson_to_yaml_failure() {
     }
 }
 
+#[test]
+fn test_kson_to_toml_success() {
+    let kson = r#"server: {
+  host: "localhost"
+  ports: [8000, 8001]
+}"#;
+    let result = Kson::to_toml(kson, transpile_options::Toml::new(true));
+    match result {
+        Err(_) => panic!("expected success, found failure"),
+        Ok(success) => {
+            // Nested objects become tables and lists become arrays.
+            let output = success.output();
+            assert!(output.contains("[server]"));
+            assert!(output.contains(r#"host = "localhost""#));
+            assert!(output.contains("ports = [8000, 8001]"));
+        }
+    }
+}
+
+#[test]
+fn test_kson_to_toml_failure() {
+    let result = Kson::to_toml("key: [1, 2, 3, 4", transpile_options::Toml::new(true));
+    match result {
+        Ok(_) => panic!("expected failure, found success"),
+        Err(failure) => {
+            let output = messages_to_string(&failure.errors());
+            insta::assert_snapshot!(output, @"0,5 to 0,16 - Unclosed list\n");
+        }
+    }
+}
+
 #[test]
 fn test_kson_analysis() {
     let analysis = Kson::analyze("key: [1, 2, 3, 4]");
@@ -205,6 +252,18 @@ fn test_kson_analysis() {
     ");
 }
 
+#[test]
+fn test_token_type_from_name_round_trips() {
+    // Every variant's Kotlin name must parse back to the same variant, keeping
+    // the Rust and Kotlin enum definitions verifiably in sync.
+    for token_type in TokenType::all() {
+        let name = token_type.name();
+        let parsed = TokenType::from_name(&name).expect("every variant name parses");
+        assert_eq!(parsed.name(), name);
+    }
+    assert!(TokenType::from_name("NOT_A_TOKEN").is_none());
+}
+
 #[test]
 fn test_kson_validate_schema() {
     let result = Kson::parse_schema(r#"{ "type": "string" }"#);
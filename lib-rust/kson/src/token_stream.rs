@@ -0,0 +1,59 @@
+//! A pull-based token stream over KSON source.
+//!
+//! [`Analysis::tokens`] hands back a flat `Vec<Token>`; [`TokenStream`] wraps it
+//! in a fused [`Iterator`] with convenient semantics for consumers that walk the
+//! tokens one at a time: it yields one [`Token`] per `next()`, emits
+//! [`TokenType::Eof`] exactly once, and then fuses (every subsequent `next()`
+//! returns `None`), so callers can stop early or drive it from an adapter
+//! without tracking the `Eof` sentinel themselves. The token vector is still
+//! produced eagerly by the JVM analysis; the stream is an ergonomic cursor over
+//! it, not a lazy re-tokenizer.
+
+use crate::{Analysis, Kson, Token, TokenType};
+
+/// A fused iterator over a document's tokens. Construct one with
+/// [`TokenStream::new`] (from source) or [`TokenStream::from_analysis`].
+pub struct TokenStream {
+    tokens: std::vec::IntoIter<Token>,
+    fused: bool,
+}
+
+impl TokenStream {
+    /// Analyze `source` and stream its tokens.
+    pub fn new(source: &str) -> Self {
+        Self::from_analysis(&Kson::analyze(source, None))
+    }
+
+    /// Stream the tokens of an already-computed [`Analysis`].
+    pub fn from_analysis(analysis: &Analysis) -> Self {
+        Self {
+            tokens: analysis.tokens().into_iter(),
+            fused: false,
+        }
+    }
+}
+
+impl Iterator for TokenStream {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.fused {
+            return None;
+        }
+        match self.tokens.next() {
+            Some(token) => {
+                if matches!(token.token_type(), TokenType::Eof) {
+                    // Eof is emitted once; everything after it fuses.
+                    self.fused = true;
+                }
+                Some(token)
+            }
+            None => {
+                self.fused = true;
+                None
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for TokenStream {}
@@ -0,0 +1,86 @@
+//! Reverse lookups for [`TokenType`].
+//!
+//! The generated bindings only go one way: [`TokenType::to_kotlin_object`] and
+//! [`TokenType::name`], plus the `FromKotlinObject` impl that reads a Kotlin
+//! enum back by ordinal. This module adds the name-based inverse
+//! [`TokenType::from_name`], needed whenever a token that was serialized or
+//! cached as a type *name* — test fixtures expressed as name sequences,
+//! protocol round-tripping, incremental re-lexing — must be fed back into Rust.
+//! [`TokenType::all`] enumerates every variant so callers (and tests) can assert
+//! the Rust and Kotlin enums stay in sync.
+
+use crate::TokenType;
+
+impl TokenType {
+    /// Every [`TokenType`] variant, in declaration order.
+    pub fn all() -> &'static [TokenType] {
+        &[
+            TokenType::CurlyBraceL,
+            TokenType::CurlyBraceR,
+            TokenType::SquareBracketL,
+            TokenType::SquareBracketR,
+            TokenType::AngleBracketL,
+            TokenType::AngleBracketR,
+            TokenType::Colon,
+            TokenType::Dot,
+            TokenType::EndDash,
+            TokenType::Comma,
+            TokenType::Comment,
+            TokenType::EmbedOpenDelim,
+            TokenType::EmbedCloseDelim,
+            TokenType::EmbedTag,
+            TokenType::EmbedPreambleNewline,
+            TokenType::EmbedContent,
+            TokenType::False,
+            TokenType::UnquotedString,
+            TokenType::IllegalChar,
+            TokenType::ListDash,
+            TokenType::Null,
+            TokenType::Number,
+            TokenType::StringOpenQuote,
+            TokenType::StringCloseQuote,
+            TokenType::StringContent,
+            TokenType::True,
+            TokenType::Whitespace,
+            TokenType::Eof,
+        ]
+    }
+
+    /// Parse a [`TokenType`] from its Kotlin enum-constant name (the
+    /// `SCREAMING_SNAKE_CASE` string [`TokenType::name`] returns), or `None` if
+    /// no variant matches.
+    pub fn from_name(name: &str) -> Option<TokenType> {
+        let variant = match name {
+            "CURLY_BRACE_L" => TokenType::CurlyBraceL,
+            "CURLY_BRACE_R" => TokenType::CurlyBraceR,
+            "SQUARE_BRACKET_L" => TokenType::SquareBracketL,
+            "SQUARE_BRACKET_R" => TokenType::SquareBracketR,
+            "ANGLE_BRACKET_L" => TokenType::AngleBracketL,
+            "ANGLE_BRACKET_R" => TokenType::AngleBracketR,
+            "COLON" => TokenType::Colon,
+            "DOT" => TokenType::Dot,
+            "END_DASH" => TokenType::EndDash,
+            "COMMA" => TokenType::Comma,
+            "COMMENT" => TokenType::Comment,
+            "EMBED_OPEN_DELIM" => TokenType::EmbedOpenDelim,
+            "EMBED_CLOSE_DELIM" => TokenType::EmbedCloseDelim,
+            "EMBED_TAG" => TokenType::EmbedTag,
+            "EMBED_PREAMBLE_NEWLINE" => TokenType::EmbedPreambleNewline,
+            "EMBED_CONTENT" => TokenType::EmbedContent,
+            "FALSE" => TokenType::False,
+            "UNQUOTED_STRING" => TokenType::UnquotedString,
+            "ILLEGAL_CHAR" => TokenType::IllegalChar,
+            "LIST_DASH" => TokenType::ListDash,
+            "NULL" => TokenType::Null,
+            "NUMBER" => TokenType::Number,
+            "STRING_OPEN_QUOTE" => TokenType::StringOpenQuote,
+            "STRING_CLOSE_QUOTE" => TokenType::StringCloseQuote,
+            "STRING_CONTENT" => TokenType::StringContent,
+            "TRUE" => TokenType::True,
+            "WHITESPACE" => TokenType::Whitespace,
+            "EOF" => TokenType::Eof,
+            _ => return None,
+        };
+        Some(variant)
+    }
+}
@@ -0,0 +1,105 @@
+//! Render a [`KsonValue`] back into indented KSON text.
+//!
+//! Callers who construct or modify a [`KsonValue`] tree programmatically need a
+//! way to print it; [`KsonValue::format`] walks the tree with a caller-supplied
+//! [`IndentType`], emitting one entry/element per line at a depth scaled by the
+//! configured unit ([`Spaces::size`](crate::indent_type::Spaces::size) spaces or
+//! a single tab per level) and rendering scalars inline. Unlike
+//! [`to_canonical_string`](KsonValue::to_canonical_string), which sorts object
+//! keys, it preserves the tree's own property order via
+//! [`properties_ordered`](crate::kson_value::KsonObject::properties_ordered).
+
+use crate::{kson_value, IndentType, KsonValue};
+
+impl KsonValue {
+    /// Render this value as indented KSON text using `indent` for nesting.
+    pub fn format(&self, indent: &IndentType) -> String {
+        let mut out = String::new();
+        write_value(self, indent, 0, &mut out);
+        out
+    }
+}
+
+fn write_indent(indent: &IndentType, depth: usize, out: &mut String) {
+    match indent {
+        IndentType::Tabs(_) => {
+            for _ in 0..depth {
+                out.push('\t');
+            }
+        }
+        IndentType::Spaces(spaces) => {
+            let width = spaces.size().max(0) as usize;
+            for _ in 0..depth * width {
+                out.push(' ');
+            }
+        }
+    }
+}
+
+fn write_value(value: &KsonValue, indent: &IndentType, depth: usize, out: &mut String) {
+    match value {
+        KsonValue::KsonNull(_) => out.push_str("null"),
+        KsonValue::KsonBoolean(b) => out.push_str(if b.value() { "true" } else { "false" }),
+        KsonValue::KsonString(s) => write_quoted(&s.value(), out),
+        KsonValue::KsonEmbed(e) => write_quoted(&e.content(), out),
+        KsonValue::KsonNumber(n) => match n {
+            kson_value::KsonNumber::Integer(i) => out.push_str(&i.value().to_string()),
+            kson_value::KsonNumber::Decimal(d) => out.push_str(&d.value().to_string()),
+        },
+        KsonValue::KsonArray(a) => {
+            let elements = a.elements();
+            if elements.is_empty() {
+                out.push_str("[]");
+                return;
+            }
+            out.push_str("[\n");
+            for (i, element) in elements.iter().enumerate() {
+                write_indent(indent, depth + 1, out);
+                write_value(element, indent, depth + 1, out);
+                if i + 1 < elements.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            write_indent(indent, depth, out);
+            out.push(']');
+        }
+        KsonValue::KsonObject(o) => {
+            let properties = o.properties_ordered();
+            if properties.is_empty() {
+                out.push_str("{}");
+                return;
+            }
+            out.push_str("{\n");
+            let mut first = true;
+            for (key, value) in &properties {
+                if !first {
+                    out.push_str(",\n");
+                }
+                first = false;
+                write_indent(indent, depth + 1, out);
+                write_quoted(key, out);
+                out.push_str(": ");
+                write_value(value, indent, depth + 1, out);
+            }
+            out.push('\n');
+            write_indent(indent, depth, out);
+            out.push('}');
+        }
+    }
+}
+
+fn write_quoted(s: &str, out: &mut String) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,102 @@
+//! Diagnostics aggregation over [`Failure`](crate::result::Failure) results.
+//!
+//! [`Failure::errors`](crate::result::Failure::errors) returns a flat
+//! `Vec<Message>`, which gets unwieldy once a document produces dozens of
+//! problems. This module buckets those messages by [`MessageSeverity`] and by
+//! source line, and reports per-severity counts, so triage logic lives in the
+//! Rust API instead of being re-implemented by every consumer.
+
+use std::collections::BTreeMap;
+
+use crate::{result, schema_result, Message, MessageSeverity};
+
+/// Ordered from least to most severe, so a [`BTreeMap`] keyed on severity lists
+/// warnings before errors and `>=` comparisons mean "at least this severe".
+impl MessageSeverity {
+    fn rank(self) -> u8 {
+        match self {
+            MessageSeverity::Warning => 0,
+            MessageSeverity::Error => 1,
+        }
+    }
+}
+
+impl PartialEq for MessageSeverity {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl Eq for MessageSeverity {}
+
+impl PartialOrd for MessageSeverity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MessageSeverity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+fn group_by_severity(messages: Vec<Message>) -> BTreeMap<MessageSeverity, Vec<Message>> {
+    let mut grouped: BTreeMap<MessageSeverity, Vec<Message>> = BTreeMap::new();
+    for message in messages {
+        grouped.entry(message.severity()).or_default().push(message);
+    }
+    grouped
+}
+
+fn filter_severity(messages: Vec<Message>, min: MessageSeverity) -> Vec<Message> {
+    messages
+        .into_iter()
+        .filter(|m| m.severity() >= min)
+        .collect()
+}
+
+fn errors_at_line(messages: Vec<Message>, line: i32) -> Vec<Message> {
+    messages
+        .into_iter()
+        .filter(|m| m.start().line() == line)
+        .collect()
+}
+
+fn severity_counts(messages: Vec<Message>) -> BTreeMap<MessageSeverity, usize> {
+    let mut counts: BTreeMap<MessageSeverity, usize> = BTreeMap::new();
+    for message in messages {
+        *counts.entry(message.severity()).or_default() += 1;
+    }
+    counts
+}
+
+macro_rules! triage_impl {
+    ($ty:ty) => {
+        impl $ty {
+            /// Bucket every error by [`MessageSeverity`], least severe first.
+            pub fn group_by_severity(&self) -> BTreeMap<MessageSeverity, Vec<Message>> {
+                group_by_severity(self.errors())
+            }
+
+            /// Return every error at least as severe as `min`.
+            pub fn filter_severity(&self, min: MessageSeverity) -> Vec<Message> {
+                filter_severity(self.errors(), min)
+            }
+
+            /// Return every error whose span starts on `line`.
+            pub fn errors_at_line(&self, line: i32) -> Vec<Message> {
+                errors_at_line(self.errors(), line)
+            }
+
+            /// Count the errors at each [`MessageSeverity`], so callers can, for
+            /// example, treat warnings as non-fatal while still surfacing them.
+            pub fn severity_counts(&self) -> BTreeMap<MessageSeverity, usize> {
+                severity_counts(self.errors())
+            }
+        }
+    };
+}
+
+triage_impl!(result::Failure);
+triage_impl!(schema_result::Failure);
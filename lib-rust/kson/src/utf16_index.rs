@@ -0,0 +1,110 @@
+//! Byte ⇄ UTF-16 offset mapping for token spans.
+//!
+//! KSON's spans are naturally expressed in UTF-8 byte offsets, but the
+//! `org.kson.api` JVM consumers index every `String` in UTF-16 code units.
+//! [`Utf16Index`] scans the source once, recording a `(byte, utf16)` checkpoint
+//! at each line start, and then converts an arbitrary byte offset to its UTF-16
+//! position — and back — by binary-searching for the nearest checkpoint and
+//! re-scanning the short remaining slice. Offsets that land inside a multi-byte
+//! char snap to the enclosing char boundary, and an offset at or past EOF maps
+//! to the total UTF-16 length. This lets editor integrations highlight tokens
+//! without re-deriving positions on the JVM side.
+
+use std::ops::Range;
+
+/// A sparse index over a source string mapping byte offsets to UTF-16 code-unit
+/// offsets and vice versa.
+pub struct Utf16Index<'a> {
+    source: &'a str,
+    /// One `(byte_offset, utf16_offset)` checkpoint per line start, sorted by
+    /// both coordinates (they increase together). The document length is kept
+    /// separately in `utf16_len`.
+    checkpoints: Vec<(usize, usize)>,
+    utf16_len: usize,
+}
+
+impl<'a> Utf16Index<'a> {
+    /// Build an index over `source`, recording a checkpoint at the start of each
+    /// line.
+    pub fn new(source: &'a str) -> Self {
+        let mut checkpoints = vec![(0, 0)];
+        let mut byte = 0;
+        let mut utf16 = 0;
+        let mut at_line_start = false;
+        for ch in source.chars() {
+            if at_line_start {
+                checkpoints.push((byte, utf16));
+                at_line_start = false;
+            }
+            byte += ch.len_utf8();
+            utf16 += ch.len_utf16();
+            if ch == '\n' {
+                at_line_start = true;
+            }
+        }
+        Self {
+            source,
+            checkpoints,
+            utf16_len: utf16,
+        }
+    }
+
+    /// The total length of the source in UTF-16 code units.
+    pub fn utf16_len(&self) -> usize {
+        self.utf16_len
+    }
+
+    /// Convert a byte offset to its UTF-16 code-unit offset. An offset inside a
+    /// multi-byte char snaps down to that char's boundary; an offset at or past
+    /// EOF maps to [`utf16_len`](Self::utf16_len).
+    pub fn to_utf16(&self, byte: usize) -> usize {
+        let byte = self.snap_to_char_boundary(byte);
+        if byte >= self.source.len() {
+            return self.utf16_len;
+        }
+        let index = self
+            .checkpoints
+            .partition_point(|&(b, _)| b <= byte)
+            .saturating_sub(1);
+        let (start, mut utf16) = self.checkpoints[index];
+        for ch in self.source[start..byte].chars() {
+            utf16 += ch.len_utf16();
+        }
+        utf16
+    }
+
+    /// Convert a UTF-16 code-unit offset back to a byte offset. An offset at or
+    /// past [`utf16_len`](Self::utf16_len) maps to the source length.
+    pub fn to_byte(&self, utf16: usize) -> usize {
+        if utf16 >= self.utf16_len {
+            return self.source.len();
+        }
+        let index = self
+            .checkpoints
+            .partition_point(|&(_, u)| u <= utf16)
+            .saturating_sub(1);
+        let (mut byte, mut cursor) = self.checkpoints[index];
+        for ch in self.source[byte..].chars() {
+            if cursor >= utf16 {
+                break;
+            }
+            byte += ch.len_utf8();
+            cursor += ch.len_utf16();
+        }
+        byte
+    }
+
+    /// Convert a byte range to the corresponding UTF-16 code-unit range.
+    pub fn to_utf16_range(&self, range: Range<usize>) -> Range<usize> {
+        self.to_utf16(range.start)..self.to_utf16(range.end)
+    }
+
+    /// Snap `byte` down to the nearest char boundary at or before it.
+    fn snap_to_char_boundary(&self, byte: usize) -> usize {
+        let mut byte = byte.min(self.source.len());
+        while byte > 0 && !self.source.is_char_boundary(byte) {
+            byte -= 1;
+        }
+        byte
+    }
+}
@@ -1,12 +1,23 @@
 use crate::KSON_SYMBOLS;
 use kson_sys::*;
+use std::marker::PhantomData;
 
 pub(crate) struct OwnedKotlinPtr {
     pub(crate) inner: kson_KNativePtr,
 }
 
+impl OwnedKotlinPtr {
+    /// Take ownership of `inner`, registering it with the debug allocation
+    /// registry (see [`ptr_registry`]) so double-frees and leaks can be caught.
+    pub(crate) fn new(inner: kson_KNativePtr) -> Self {
+        ptr_registry::register(inner);
+        Self { inner }
+    }
+}
+
 impl Drop for OwnedKotlinPtr {
     fn drop(&mut self) {
+        ptr_registry::unregister(self.inner);
         unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(self.inner) };
     }
 }
@@ -37,10 +48,77 @@ pub(crate) trait FromKotlinObject {
     fn from_kotlin_object(obj: kson_KNativePtr) -> Self;
 }
 
+/// A wrapper that knows the Kotlin runtime type backing it, so a raw
+/// `kson_KNativePtr` can be checked against that type before conversion. The
+/// [`impl_kotlin_typed!`] macro implements this from the generated `kson_KType`
+/// statics.
+pub(crate) trait KotlinTyped {
+    /// The Kotlin runtime type this wrapper corresponds to.
+    fn kotlin_type() -> KotlinType;
+}
+
+/// Attempt a runtime-checked downcast of `obj` to `T`.
+///
+/// Calls the Kotlin `IsInstance` function against `T`'s [`KotlinType`] and only
+/// invokes [`FromKotlinObject::from_kotlin_object`] when it matches, returning
+/// `None` otherwise. This turns the otherwise-blind conversions into checked
+/// ones, letting callers ask "is this `Any` really a KSON array/object/number?"
+/// before converting. On a match the returned value assumes ownership of the
+/// pointer, exactly as the enum-downcast path does.
+pub(crate) fn try_cast<T: FromKotlinObject + KotlinTyped>(obj: &KsonPtr) -> Option<T> {
+    let ptr = obj.inner.inner;
+    let is_instance = KSON_SYMBOLS.IsInstance.unwrap();
+    if unsafe { is_instance(ptr, T::kotlin_type().inner) } {
+        Some(T::from_kotlin_object(ptr))
+    } else {
+        None
+    }
+}
+
 pub(crate) trait ToKotlinObject {
     fn to_kotlin_object(&self) -> kson_KNativePtr;
 }
 
+/// A marker for data that is safe to hand to a Kotlin object which may outlive
+/// the Rust call.
+///
+/// A Kotlin handle can retain whatever we pass it indefinitely, so shuttling a
+/// value that carries a borrowed reference or a raw pointer across the FFI
+/// boundary is unsound — the referent may be freed while Kotlin still holds the
+/// pointer. `KotlinSafe` is an auto trait with negative impls for references,
+/// raw pointers, and interior-mutability cells, so only self-contained,
+/// non-borrowing data implements it. Bounding the conversion entry points on it
+/// turns today's implicit "trust the caller" contract into a compile-time
+/// guarantee.
+///
+/// # Safety
+///
+/// Implementors must not expose Rust-owned memory whose lifetime is shorter
+/// than the Kotlin handle that receives it.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be converted into a Kotlin handle",
+    label = "carries borrowed references or raw pointers",
+    note = "only self-contained, non-borrowing data may cross the FFI boundary"
+)]
+pub(crate) unsafe auto trait KotlinSafe {}
+
+impl<T: ?Sized> !KotlinSafe for *const T {}
+impl<T: ?Sized> !KotlinSafe for *mut T {}
+impl<T: ?Sized> !KotlinSafe for &T {}
+impl<T: ?Sized> !KotlinSafe for &mut T {}
+// Interior mutability is built on `UnsafeCell`; excluding it here makes `Cell`,
+// `RefCell`, and friends `!KotlinSafe` by auto-trait propagation.
+impl<T: ?Sized> !KotlinSafe for std::cell::UnsafeCell<T> {}
+
+/// Convert `value` into a Kotlin handle, statically rejecting any type that
+/// carries borrowed references or raw pointers via the [`KotlinSafe`] bound.
+/// This is the safe entry point onto the [`ToKotlinObject`] path.
+pub(crate) fn to_kotlin_object_safe<T: ToKotlinObject + KotlinSafe + ?Sized>(
+    value: &T,
+) -> kson_KNativePtr {
+    value.to_kotlin_object()
+}
+
 impl ToKotlinObject for kson_KNativePtr {
     fn to_kotlin_object(&self) -> kson_KNativePtr {
         *self
@@ -304,3 +382,378 @@ pub(crate) fn from_kotlin_string_map<V: FromKotlinObject>(
 pub(crate) struct KsonPtr {
     pub(crate) inner: std::sync::Arc<OwnedKotlinPtr>,
 }
+
+/// A lazy [`Iterator`] over a Kotlin `List`, wrapping the `SimpleListIterator`
+/// FFI so elements are pulled and converted one at a time. Unlike
+/// [`from_kotlin_list`], which drains the whole list into a `Vec` up front, this
+/// keeps at most a single element materialized in Rust at a time, which lets a
+/// caller touch only the first few entries of a large parsed document. The
+/// underlying iterator pointer is disposed on `Drop`, exactly as the eager loop
+/// does.
+pub(crate) struct KotlinListIter<T: FromKotlinObject> {
+    iterator: kson_kref_org_kson_SimpleListIterator,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: FromKotlinObject> KotlinListIter<T> {
+    pub(crate) fn new(list: kson_kref_kotlin_collections_List) -> Self {
+        let iterator = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleListIterator
+                .SimpleListIterator
+                .unwrap()(list)
+        };
+        Self {
+            iterator,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromKotlinObject> Iterator for KotlinListIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let next = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleListIterator
+                .next
+                .unwrap()(self.iterator)
+        };
+        if next.pinned.is_null() {
+            self.done = true;
+            return None;
+        }
+        Some(FromKotlinObject::from_kotlin_object(next.pinned))
+    }
+}
+
+// Once `next` observes a null `pinned`, the Kotlin iterator is exhausted and
+// stays exhausted, so iteration is fused.
+impl<T: FromKotlinObject> std::iter::FusedIterator for KotlinListIter<T> {}
+
+impl<T: FromKotlinObject> Drop for KotlinListIter<T> {
+    fn drop(&mut self) {
+        unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(self.iterator.pinned) };
+    }
+}
+
+/// A lazy [`Iterator`] over a Kotlin `Map`, yielding `(key, value)` pairs one at
+/// a time. The companion to [`KotlinListIter`] for [`from_kotlin_string_map`];
+/// the owning iterator pointer, and each consumed entry, are disposed as the
+/// eager loop does.
+pub(crate) struct KotlinMapIter<V: FromKotlinObject> {
+    iterator: kson_kref_org_kson_SimpleMapIterator,
+    done: bool,
+    _marker: PhantomData<fn() -> V>,
+}
+
+impl<V: FromKotlinObject> KotlinMapIter<V> {
+    pub(crate) fn new(map: kson_kref_kotlin_collections_Map) -> Self {
+        let iterator = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleMapIterator
+                .SimpleMapIterator
+                .unwrap()(map)
+        };
+        Self {
+            iterator,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<V: FromKotlinObject> Iterator for KotlinMapIter<V> {
+    type Item = (String, V);
+
+    fn next(&mut self) -> Option<(String, V)> {
+        if self.done {
+            return None;
+        }
+        let next = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleMapIterator
+                .next
+                .unwrap()(self.iterator)
+        };
+        if next.pinned.is_null() {
+            self.done = true;
+            return None;
+        }
+
+        let key = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleMapEntry
+                .get_key
+                .unwrap()(next)
+        };
+        let key_string = to_string(key.pinned);
+
+        let value = unsafe {
+            KSON_SYMBOLS
+                .kotlin
+                .root
+                .org
+                .kson
+                .SimpleMapEntry
+                .get_value
+                .unwrap()(next)
+        };
+        let value = FromKotlinObject::from_kotlin_object(value.pinned);
+
+        unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(next.pinned) };
+        Some((key_string, value))
+    }
+}
+
+impl<V: FromKotlinObject> std::iter::FusedIterator for KotlinMapIter<V> {}
+
+impl<V: FromKotlinObject> Drop for KotlinMapIter<V> {
+    fn drop(&mut self) {
+        unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(self.iterator.pinned) };
+    }
+}
+
+/// The error returned when a [`ThreadBound`] handle is touched from a thread
+/// other than the one that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WrongThread {
+    /// The thread that constructed (and owns) the handle.
+    pub(crate) owner: std::thread::ThreadId,
+    /// The thread that attempted the access.
+    pub(crate) accessor: std::thread::ThreadId,
+}
+
+impl std::fmt::Display for WrongThread {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "thread-bound kotlin handle owned by {:?} accessed from {:?}",
+            self.owner, self.accessor
+        )
+    }
+}
+
+impl std::error::Error for WrongThread {}
+
+// A pointer queued for disposal on its owning thread. The raw pointer is only
+// ever dereferenced (disposed) on that thread, so shuttling the bare value
+// through the global queue is sound.
+struct QueuedPtr(kson_KNativePtr);
+unsafe impl Send for QueuedPtr {}
+
+static DROP_QUEUE: std::sync::Mutex<Vec<(std::thread::ThreadId, QueuedPtr)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// A handle to a Kotlin object whose methods we are *not* confident are `Sync`.
+///
+/// Unlike [`KsonPtr`]/`Arc<OwnedKotlinPtr>`, which optimistically share a
+/// pointer across threads, `ThreadBound` records the [`ThreadId`] that created
+/// it and refuses access from any other thread. Crucially, it also routes
+/// `DisposeStablePointer` back to the owning thread via a drop queue: a value
+/// sent across threads and dropped there is never freed from the wrong one.
+/// Callers pick safety (this) vs. optimistic sharing ([`KsonPtr`]) explicitly.
+///
+/// [`ThreadId`]: std::thread::ThreadId
+pub(crate) struct ThreadBound {
+    owner: std::thread::ThreadId,
+    ptr: kson_KNativePtr,
+}
+
+// Safety: the handle may be *moved* to another thread, but `get` gates every
+// access on the owning thread and `drop` routes disposal back to it, so no
+// Kotlin method or free is ever invoked off-thread.
+unsafe impl Send for ThreadBound {}
+
+impl ThreadBound {
+    /// Bind `ptr` to the current thread.
+    pub(crate) fn new(ptr: kson_KNativePtr) -> Self {
+        Self {
+            owner: std::thread::current().id(),
+            ptr,
+        }
+    }
+
+    /// Borrow the underlying pointer, erroring if called off the owning thread.
+    pub(crate) fn get(&self) -> Result<kson_KNativePtr, WrongThread> {
+        let accessor = std::thread::current().id();
+        if accessor == self.owner {
+            Ok(self.ptr)
+        } else {
+            Err(WrongThread {
+                owner: self.owner,
+                accessor,
+            })
+        }
+    }
+
+    /// Dispose any pointers that were queued for the current thread because
+    /// their [`ThreadBound`] owner was dropped elsewhere. A thread that hands
+    /// `ThreadBound` handles to other threads should drain the queue
+    /// periodically so deferred frees make progress.
+    pub(crate) fn drain_drop_queue() {
+        let current = std::thread::current().id();
+        let mut queue = DROP_QUEUE.lock().unwrap();
+        queue.retain(|(owner, queued)| {
+            if *owner == current {
+                unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(queued.0) };
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+impl Drop for ThreadBound {
+    fn drop(&mut self) {
+        if std::thread::current().id() == self.owner {
+            unsafe { KSON_SYMBOLS.DisposeStablePointer.unwrap()(self.ptr) };
+        } else {
+            // Freeing a Kotlin pointer from the wrong thread is exactly the
+            // hazard this wrapper exists to avoid: defer to the owning thread.
+            DROP_QUEUE
+                .lock()
+                .unwrap()
+                .push((self.owner, QueuedPtr(self.ptr)));
+        }
+    }
+}
+
+/// A forward cursor over a [`KotlinListIter`] that buffers the current element,
+/// so callers can [`peek`](Cursor::peek) at it before deciding whether to
+/// [`remove`](Cursor::remove) it and advance. Modeled on the kernel `list`
+/// module's `Iter`/`Cursor` split, this walks and prunes large collections
+/// without ever materializing the whole list.
+pub(crate) struct Cursor<T: FromKotlinObject> {
+    iter: KotlinListIter<T>,
+    current: Option<T>,
+}
+
+impl<T: FromKotlinObject> Cursor<T> {
+    pub(crate) fn new(list: kson_kref_kotlin_collections_List) -> Self {
+        let mut iter = KotlinListIter::new(list);
+        let current = iter.next();
+        Self { iter, current }
+    }
+
+    /// The element the cursor currently points at, or `None` once exhausted.
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.current.as_ref()
+    }
+
+    /// Remove the current element from the walk, returning it, and advance the
+    /// cursor to the next one.
+    pub(crate) fn remove(&mut self) -> Option<T> {
+        let removed = self.current.take();
+        self.current = self.iter.next();
+        removed
+    }
+}
+
+/// Debug-mode allocation registry for Kotlin pointers.
+///
+/// Every pointer handed to [`OwnedKotlinPtr::new`] is recorded here together
+/// with the backtrace of where it was created, and removed again when the
+/// `OwnedKotlinPtr` (or the [`KsonPtr`] wrapping it) is dropped. Disposing a
+/// pointer that was never tracked — the signature of a double-free — emits a
+/// diagnostic, and [`leaked_pointers`] lets tests assert the registry is empty
+/// after an operation. The whole module compiles to no-ops unless debug
+/// assertions are on or the `track-ptrs` feature is enabled, so release builds
+/// pay nothing.
+pub(crate) mod ptr_registry {
+    use kson_sys::kson_KNativePtr;
+
+    #[cfg(any(debug_assertions, feature = "track-ptrs"))]
+    mod imp {
+        use super::kson_KNativePtr;
+        use std::backtrace::Backtrace;
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+
+        static REGISTRY: Mutex<Option<HashMap<usize, Backtrace>>> = Mutex::new(None);
+
+        fn with<R>(f: impl FnOnce(&mut HashMap<usize, Backtrace>) -> R) -> R {
+            let mut guard = REGISTRY.lock().unwrap();
+            f(guard.get_or_insert_with(HashMap::new))
+        }
+
+        pub(super) fn register(ptr: kson_KNativePtr) {
+            let key = ptr as usize;
+            with(|map| {
+                if map.insert(key, Backtrace::capture()).is_some() {
+                    eprintln!("kson: pointer {key:#x} registered twice (double ownership?)");
+                }
+            });
+        }
+
+        pub(super) fn unregister(ptr: kson_KNativePtr) {
+            let key = ptr as usize;
+            with(|map| {
+                if map.remove(&key).is_none() {
+                    eprintln!(
+                        "kson: disposed untracked pointer {key:#x} (double-free?)\n{}",
+                        Backtrace::capture()
+                    );
+                }
+            });
+        }
+
+        pub(super) fn leaked_pointers() -> Vec<usize> {
+            with(|map| map.keys().copied().collect())
+        }
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "track-ptrs")))]
+    mod imp {
+        use super::kson_KNativePtr;
+
+        pub(super) fn register(_ptr: kson_KNativePtr) {}
+        pub(super) fn unregister(_ptr: kson_KNativePtr) {}
+        pub(super) fn leaked_pointers() -> Vec<usize> {
+            Vec::new()
+        }
+    }
+
+    /// Record `ptr` as a live allocation.
+    pub(crate) fn register(ptr: kson_KNativePtr) {
+        imp::register(ptr);
+    }
+
+    /// Drop `ptr` from the live set, warning if it was never tracked.
+    pub(crate) fn unregister(ptr: kson_KNativePtr) {
+        imp::unregister(ptr);
+    }
+
+    /// The addresses of every pointer still considered live. Empty in release
+    /// builds without the `track-ptrs` feature.
+    pub(crate) fn leaked_pointers() -> Vec<usize> {
+        imp::leaked_pointers()
+    }
+}